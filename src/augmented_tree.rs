@@ -0,0 +1,214 @@
+//! An AVL tree where every node also stores a summary of its subtree,
+//! shared by [`DiscreteIntervalMultiMap`] and
+//! [`DiscreteRangeAggregateMap`].
+//!
+//! The `BTreeMap` behind [`DiscreteRangeMap`] has no way to store extra
+//! data in its nodes or to hook into its rebalancing, which keeping a
+//! summary of each subtree up to date needs, hence this separate tree.
+//!
+//! [`DiscreteIntervalMultiMap`]: crate::DiscreteIntervalMultiMap
+//! [`DiscreteRangeAggregateMap`]: crate::DiscreteRangeAggregateMap
+//! [`DiscreteRangeMap`]: crate::DiscreteRangeMap
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// An item stored in a [`Tree`] along with how to order it and how to
+/// summarize a subtree with it at the root.
+pub(crate) trait Augmented<S> {
+	type Position: Ord;
+
+	fn position(&self) -> Self::Position;
+	/// Returns the summary of a subtree with this item at its root and
+	/// the given summaries of its left and right subtrees.
+	fn summarize(&self, left: Option<&S>, right: Option<&S>) -> S;
+}
+
+pub(crate) type Tree<T, S> = Option<Box<Node<T, S>>>;
+
+#[derive(Clone)]
+pub(crate) struct Node<T, S> {
+	pub(crate) item: T,
+	// the summary of this node's subtree
+	pub(crate) summary: S,
+	height: usize,
+	pub(crate) left: Tree<T, S>,
+	pub(crate) right: Tree<T, S>,
+}
+
+/// Inserts an item into the tree, after any items with an equal
+/// position.
+pub(crate) fn insert<T, S>(tree: Tree<T, S>, item: T) -> Box<Node<T, S>>
+where
+	T: Augmented<S>,
+{
+	let Some(mut node) = tree else {
+		return Box::new(Node {
+			summary: item.summarize(None, None),
+			item,
+			height: 1,
+			left: None,
+			right: None,
+		});
+	};
+
+	if item.position() < node.item.position() {
+		node.left = Some(insert(node.left.take(), item));
+	} else {
+		node.right = Some(insert(node.right.take(), item));
+	}
+
+	return rebalance(node);
+}
+
+/// Removes an item with the given position from the tree, if any.
+pub(crate) fn remove<T, S>(
+	tree: &mut Tree<T, S>,
+	position: &T::Position,
+) -> Option<T>
+where
+	T: Augmented<S>,
+{
+	let mut node = tree.take()?;
+
+	let removed = match position.cmp(&node.item.position()) {
+		Ordering::Less => remove(&mut node.left, position),
+		Ordering::Greater => remove(&mut node.right, position),
+		Ordering::Equal => {
+			let Node {
+				item, left, right, ..
+			} = *node;
+
+			*tree = match (left, right) {
+				(left, None) => left,
+				(None, right) => right,
+				(left, mut right) => {
+					let mut successor = remove_min(&mut right).unwrap();
+					successor.left = left;
+					successor.right = right;
+					Some(rebalance(successor))
+				}
+			};
+
+			return Some(item);
+		}
+	};
+
+	*tree = Some(rebalance(node));
+
+	return removed;
+}
+
+/// Moves every item of a tree, mapped by `f`, onto the end of `output`
+/// in order.
+pub(crate) fn drain_into<T, S, U, F>(
+	tree: Tree<T, S>,
+	output: &mut Vec<U>,
+	f: &mut F,
+) where
+	F: FnMut(T) -> U,
+{
+	if let Some(node) = tree {
+		let Node {
+			item, left, right, ..
+		} = *node;
+
+		drain_into(left, output, f);
+		output.push(f(item));
+		drain_into(right, output, f);
+	}
+}
+
+fn height<T, S>(tree: &Tree<T, S>) -> usize {
+	tree.as_ref().map_or(0, |node| node.height)
+}
+
+/// Recalculates the height and summary of a node from its children.
+fn update<T, S>(node: &mut Node<T, S>)
+where
+	T: Augmented<S>,
+{
+	node.height = 1 + height(&node.left).max(height(&node.right));
+	node.summary = node.item.summarize(
+		node.left.as_ref().map(|left| &left.summary),
+		node.right.as_ref().map(|right| &right.summary),
+	);
+}
+
+fn rotate_right<T, S>(mut node: Box<Node<T, S>>) -> Box<Node<T, S>>
+where
+	T: Augmented<S>,
+{
+	let mut left = node.left.take().unwrap();
+	node.left = left.right.take();
+	update(&mut node);
+	left.right = Some(node);
+	update(&mut left);
+
+	return left;
+}
+
+fn rotate_left<T, S>(mut node: Box<Node<T, S>>) -> Box<Node<T, S>>
+where
+	T: Augmented<S>,
+{
+	let mut right = node.right.take().unwrap();
+	node.right = right.left.take();
+	update(&mut node);
+	right.left = Some(node);
+	update(&mut right);
+
+	return right;
+}
+
+/// Updates a node whose children's heights differ by at most two and
+/// rotates it so that they differ by at most one.
+fn rebalance<T, S>(mut node: Box<Node<T, S>>) -> Box<Node<T, S>>
+where
+	T: Augmented<S>,
+{
+	update(&mut node);
+
+	let left_height = height(&node.left);
+	let right_height = height(&node.right);
+
+	if left_height > right_height + 1 {
+		let left = node.left.take().unwrap();
+		node.left = Some(if height(&left.left) < height(&left.right) {
+			rotate_left(left)
+		} else {
+			left
+		});
+		return rotate_right(node);
+	}
+	if right_height > left_height + 1 {
+		let right = node.right.take().unwrap();
+		node.right = Some(if height(&right.right) < height(&right.left) {
+			rotate_right(right)
+		} else {
+			right
+		});
+		return rotate_left(node);
+	}
+
+	return node;
+}
+
+/// Removes the smallest node from a tree, if any.
+fn remove_min<T, S>(tree: &mut Tree<T, S>) -> Option<Box<Node<T, S>>>
+where
+	T: Augmented<S>,
+{
+	let mut node = tree.take()?;
+
+	if node.left.is_none() {
+		*tree = node.right.take();
+		return Some(node);
+	}
+
+	let min = remove_min(&mut node.left);
+	*tree = Some(rebalance(node));
+
+	return min;
+}
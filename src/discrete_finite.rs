@@ -17,6 +17,8 @@ You should have received a copy of the GNU Affero General Public License
 along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 */
 
+use core::iter::Step;
+
 pub trait DiscreteFinite {
 	const MIN: Self;
 	const MAX: Self;
@@ -27,11 +29,42 @@ pub trait DiscreteFinite {
 	fn down(self) -> Option<Self>
 	where
 		Self: Sized;
+
+	/// Returns the point `n` points above `self`, or `None` if there
+	/// isn't one.
+	///
+	/// The default implementation calls `up()` `n` times so you may want
+	/// to override it with a constant-time version.
+	fn up_by(self, n: u128) -> Option<Self>
+	where
+		Self: Sized,
+	{
+		let mut point = self;
+		for _ in 0..n {
+			point = point.up()?;
+		}
+		Some(point)
+	}
+	/// Returns the point `n` points below `self`, or `None` if there
+	/// isn't one.
+	///
+	/// The default implementation calls `down()` `n` times so you may
+	/// want to override it with a constant-time version.
+	fn down_by(self, n: u128) -> Option<Self>
+	where
+		Self: Sized,
+	{
+		let mut point = self;
+		for _ in 0..n {
+			point = point.down()?;
+		}
+		Some(point)
+	}
 }
 
 macro_rules! foo {
     () => {};
-	($ident:ident, $($t:tt)*) => {
+	($ident:ident => $unsigned:ident, $($t:tt)*) => {
 		impl DiscreteFinite for $ident {
 			const MIN: Self = $ident::MIN;
 			const MAX: Self = $ident::MAX;
@@ -42,10 +75,112 @@ macro_rules! foo {
 			fn down(self) -> Option<Self> {
 				self.checked_sub(1)
 			}
+			// `n` is less than `2^bits` so the wrapping operations only
+			// overflow if they wrap around past `self`
+			fn up_by(self, n: u128) -> Option<Self> {
+				let n = $unsigned::try_from(n).ok()?;
+				let point = self.wrapping_add(n as $ident);
+				(point >= self).then_some(point)
+			}
+			fn down_by(self, n: u128) -> Option<Self> {
+				let n = $unsigned::try_from(n).ok()?;
+				let point = self.wrapping_sub(n as $ident);
+				(point <= self).then_some(point)
+			}
 		}
 
         foo!($($t)*);
 	};
 }
 
-foo!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128,);
+foo!(
+	u8 => u8, i8 => u8, u16 => u16, i16 => u16, u32 => u32, i32 => u32,
+	u64 => u64, i64 => u64, u128 => u128, i128 => u128, usize => usize,
+	isize => usize,
+);
+
+/// An extension of [`DiscreteFinite`] for types which can count the
+/// number of points between two points.
+pub trait DiscreteMeasure: DiscreteFinite {
+	/// Returns the number of times `up()` has to be called on `from` to
+	/// reach `to`, where `from` must not be greater than `to`.
+	///
+	/// This can't overflow since no [`DiscreteFinite`] type implemented
+	/// in this crate has more than `2^128` points.
+	fn distance(from: Self, to: Self) -> u128
+	where
+		Self: Sized;
+}
+
+macro_rules! bar {
+    () => {};
+	($ident:ident => $unsigned:ident, $($t:tt)*) => {
+		impl DiscreteMeasure for $ident {
+			fn distance(from: Self, to: Self) -> u128 {
+				// the true difference is always in `0..2^bits` so it can be
+				// recovered by reinterpreting the wrapped difference as
+				// unsigned
+				to.wrapping_sub(from) as $unsigned as u128
+			}
+		}
+
+        bar!($($t)*);
+	};
+}
+
+bar!(
+	u8 => u8, i8 => u8, u16 => u16, i16 => u16, u32 => u32, i32 => u32,
+	u64 => u64, i64 => u64, u128 => u128, i128 => u128, usize => usize,
+	isize => usize,
+);
+
+// `Step` for `char` already skips over the surrogate range
+// `0xD800..=0xDFFF` which aren't valid `char`s.
+impl DiscreteFinite for char {
+	const MIN: Self = '\0';
+	const MAX: Self = char::MAX;
+
+	fn up(self) -> Option<Self> {
+		Step::forward_checked(self, 1)
+	}
+	fn down(self) -> Option<Self> {
+		Step::backward_checked(self, 1)
+	}
+	fn up_by(self, n: u128) -> Option<Self> {
+		Step::forward_checked(self, usize::try_from(n).ok()?)
+	}
+	fn down_by(self, n: u128) -> Option<Self> {
+		Step::backward_checked(self, usize::try_from(n).ok()?)
+	}
+}
+
+impl DiscreteFinite for bool {
+	const MIN: Self = false;
+	const MAX: Self = true;
+
+	fn up(self) -> Option<Self> {
+		(!self).then_some(true)
+	}
+	fn down(self) -> Option<Self> {
+		self.then_some(false)
+	}
+}
+
+impl DiscreteMeasure for char {
+	fn distance(from: Self, to: Self) -> u128 {
+		const SURROGATES: u128 = 0xDFFF - 0xD800 + 1;
+
+		let distance = u128::from(u32::from(to) - u32::from(from));
+		if from <= '\u{D7FF}' && to >= '\u{E000}' {
+			distance - SURROGATES
+		} else {
+			distance
+		}
+	}
+}
+
+impl DiscreteMeasure for bool {
+	fn distance(from: Self, to: Self) -> u128 {
+		u128::from(to) - u128::from(from)
+	}
+}
@@ -0,0 +1,753 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+
+use serde::de::{SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::augmented_tree::{self, Augmented, Node, Tree};
+use crate::discrete_range_map::{
+	check_valid_range, invalid_range_panic, PointType, RangeType,
+};
+use crate::utils::overlaps;
+use crate::{InclusiveInterval, InvalidRangeError};
+
+/// An ordered map of possibly-overlapping ranges to values.
+///
+/// Unlike [`DiscreteRangeMap`](crate::DiscreteRangeMap), the ranges in a
+/// [`DiscreteIntervalMultiMap`] may overlap, and the same range may be
+/// inserted any number of times. Entries are ordered by the start of
+/// their ranges, then by the end of their ranges, then by the order in
+/// which they were inserted.
+///
+/// The entries are stored in a balanced binary tree where every node
+/// also stores the largest range end in its subtree, so that
+/// [`DiscreteIntervalMultiMap::overlapping()`] and
+/// [`DiscreteIntervalMultiMap::containing_point()`] can skip subtrees
+/// ending before the given range and run in `O(min(n, (k + 1) log
+/// n))` time where `k` is the number of entries yielded. This is
+/// slower than the `O(log n + k)` of a centered interval tree, which
+/// can't be updated in place.
+///
+/// This tree is separate from the `BTreeMap` behind
+/// [`DiscreteRangeMap`](crate::DiscreteRangeMap) as that `BTreeMap`
+/// has no way to store extra data in its nodes or to hook into its
+/// rebalancing, which keeping the largest range end of each subtree
+/// up to date needs.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is range over.
+///
+/// `K` is the generic type parameter for the range implementing type
+/// in the map.
+///
+/// `V` is the generic type parameter for the values associated with the
+/// keys in the map.
+///
+/// Phrasing it another way: `I` is the point type, `K` is the range type
+/// and `V` is the value type.
+///
+/// # Examples
+/// ```
+/// use discrete_range_map::test_ranges::ie;
+/// use discrete_range_map::DiscreteIntervalMultiMap;
+///
+/// let mut bookings = DiscreteIntervalMultiMap::new();
+///
+/// bookings.insert(ie(9, 12), "alice");
+/// bookings.insert(ie(10, 14), "bob");
+/// bookings.insert(ie(13, 17), "carol");
+///
+/// assert_eq!(
+/// 	bookings.containing_point(11).collect::<Vec<_>>(),
+/// 	[(&ie(9, 12), &"alice"), (&ie(10, 14), &"bob")]
+/// );
+/// ```
+pub struct DiscreteIntervalMultiMap<I, K, V> {
+	// each node's summary is the largest range end in its subtree
+	root: Tree<Entry<K, V>, I>,
+	len: usize,
+	// the number of entries ever inserted, used to order entries with
+	// equal ranges by when they were inserted
+	inserted: u64,
+}
+
+#[derive(Clone)]
+struct Entry<K, V> {
+	key: K,
+	value: V,
+	seq: u64,
+}
+
+impl<I, K, V> DiscreteIntervalMultiMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Adds a new entry to the map, regardless of whether its range
+	/// overlaps any other ranges in the map.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteIntervalMultiMap;
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(1, 8), 1);
+	/// map.insert(ie(4, 6), 2);
+	/// map.insert(ie(1, 8), 3);
+	///
+	/// assert_eq!(
+	/// 	map.iter().collect::<Vec<_>>(),
+	/// 	[(&ie(1, 8), &1), (&ie(1, 8), &3), (&ie(4, 6), &2)]
+	/// );
+	/// ```
+	pub fn insert(&mut self, range: K, value: V) {
+		invalid_range_panic(range);
+
+		let entry = Entry {
+			key: range,
+			value,
+			seq: self.inserted,
+		};
+
+		self.root = Some(augmented_tree::insert(self.root.take(), entry));
+		self.len += 1;
+		self.inserted += 1;
+	}
+
+	/// Removes every entry in the map whose range overlaps the given
+	/// range and returns them in an iterator in ascending order.
+	///
+	/// The entries are removed lazily as the iterator is advanced, if
+	/// the iterator is dropped before being fully consumed then the
+	/// remaining overlapping entries are removed at that point, similar
+	/// to [`Vec::drain()`](alloc::vec::Vec::drain).
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteIntervalMultiMap;
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(1, 4), 1);
+	/// map.insert(ie(2, 8), 2);
+	/// map.insert(ie(6, 10), 3);
+	///
+	/// assert_eq!(
+	/// 	map.remove_overlapping(ie(0, 3)).collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 1), (ie(2, 8), 2)]
+	/// );
+	/// assert_eq!(map.iter().collect::<Vec<_>>(), [(&ie(6, 10), &3)]);
+	/// ```
+	pub fn remove_overlapping<Q>(
+		&mut self,
+		range: Q,
+	) -> impl Iterator<Item = (K, V)> + '_
+	where
+		Q: RangeType<I>,
+	{
+		invalid_range_panic(range);
+
+		return RemoveOverlapping {
+			map: self,
+			range: InclusiveInterval {
+				start: range.start(),
+				end: range.end(),
+			},
+		};
+	}
+
+	/// Returns an iterator over every entry in the map whose range
+	/// overlaps the given range, in ascending order.
+	///
+	/// This runs in `O(min(n, (k + 1) log n))` time where `k` is the
+	/// number of entries yielded.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteIntervalMultiMap;
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(1, 4), 1);
+	/// map.insert(ie(2, 8), 2);
+	/// map.insert(ie(6, 10), 3);
+	///
+	/// assert_eq!(
+	/// 	map.overlapping(ie(4, 6)).collect::<Vec<_>>(),
+	/// 	[(&ie(2, 8), &2)]
+	/// );
+	/// ```
+	pub fn overlapping<Q>(&self, range: Q) -> impl Iterator<Item = (&K, &V)>
+	where
+		Q: RangeType<I>,
+	{
+		invalid_range_panic(range);
+
+		self.overlapping_entries(range)
+			.map(|entry| (&entry.key, &entry.value))
+	}
+
+	/// Returns an iterator over every entry in the map whose range
+	/// contains the given point, in ascending order.
+	///
+	/// This runs in `O(min(n, (k + 1) log n))` time where `k` is the
+	/// number of entries yielded.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteIntervalMultiMap;
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(1, 4), 1);
+	/// map.insert(ie(2, 8), 2);
+	/// map.insert(ie(6, 10), 3);
+	///
+	/// assert_eq!(
+	/// 	map.containing_point(3).collect::<Vec<_>>(),
+	/// 	[(&ie(1, 4), &1), (&ie(2, 8), &2)]
+	/// );
+	/// assert_eq!(map.containing_point(10).next(), None);
+	/// ```
+	pub fn containing_point(&self, point: I) -> impl Iterator<Item = (&K, &V)> {
+		self.overlapping(InclusiveInterval {
+			start: point,
+			end: point,
+		})
+	}
+
+	/// Returns an iterator over every entry in the map whose range is
+	/// entirely contained within the given range, in ascending order.
+	///
+	/// This visits every entry starting within the given range, so it
+	/// runs in `O(log n + m)` time where `m` is the number of entries
+	/// starting within the given range. It can't run in
+	/// `O(log n + k)` time, where `k` is the number of entries
+	/// yielded, as entries starting within the range but ending after
+	/// it can't be skipped using the largest range end of a subtree.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteIntervalMultiMap;
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(1, 4), 1);
+	/// map.insert(ie(2, 8), 2);
+	/// map.insert(ie(6, 10), 3);
+	///
+	/// assert_eq!(
+	/// 	map.contained_in(ie(2, 10)).collect::<Vec<_>>(),
+	/// 	[(&ie(2, 8), &2), (&ie(6, 10), &3)]
+	/// );
+	/// ```
+	pub fn contained_in<Q>(&self, range: Q) -> impl Iterator<Item = (&K, &V)>
+	where
+		Q: RangeType<I>,
+	{
+		invalid_range_panic(range);
+
+		let mut contained_in = ContainedIn {
+			stack: Vec::new(),
+			range: InclusiveInterval {
+				start: range.start(),
+				end: range.end(),
+			},
+		};
+		contained_in.push_left(self.root.as_deref());
+
+		return contained_in.map(|entry| (&entry.key, &entry.value));
+	}
+
+	/// Returns an iterator over every entry in the map in ascending
+	/// order.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteIntervalMultiMap;
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(6, 10), 1);
+	/// map.insert(ie(1, 4), 2);
+	///
+	/// assert_eq!(
+	/// 	map.iter().collect::<Vec<_>>(),
+	/// 	[(&ie(1, 4), &2), (&ie(6, 10), &1)]
+	/// );
+	/// ```
+	pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+		self.overlapping(InclusiveInterval {
+			start: I::MIN,
+			end: I::MAX,
+		})
+	}
+
+	/// Fallible version of [`DiscreteIntervalMultiMap::insert()`] which
+	/// returns an [`InvalidRangeError`] rather than panicking if the
+	/// given range is an invalid range.
+	pub fn try_insert(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		self.insert(range, value);
+		Ok(())
+	}
+	/// Fallible version of
+	/// [`DiscreteIntervalMultiMap::remove_overlapping()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are removed.
+	pub fn try_remove_overlapping<Q>(
+		&mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (K, V)> + '_, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.remove_overlapping(range))
+	}
+	/// Fallible version of [`DiscreteIntervalMultiMap::overlapping()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::{ie, ii};
+	/// use discrete_range_map::{
+	/// 	DiscreteIntervalMultiMap, InvalidRangeError,
+	/// };
+	///
+	/// let mut map = DiscreteIntervalMultiMap::new();
+	///
+	/// map.insert(ie(1, 4), 1);
+	///
+	/// assert_eq!(map.try_overlapping(ii(2, 8)).unwrap().count(), 1);
+	/// assert_eq!(
+	/// 	map.try_overlapping(ii(8, 2)).err(),
+	/// 	Some(InvalidRangeError { start: 8, end: 2 })
+	/// );
+	/// ```
+	pub fn try_overlapping<Q>(
+		&self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (&K, &V)>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.overlapping(range))
+	}
+	/// Fallible version of [`DiscreteIntervalMultiMap::contained_in()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	pub fn try_contained_in<Q>(
+		&self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (&K, &V)>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.contained_in(range))
+	}
+
+	fn overlapping_entries<Q>(&self, range: Q) -> Overlapping<'_, I, K, V>
+	where
+		Q: RangeType<I>,
+	{
+		let mut overlapping = Overlapping {
+			stack: Vec::new(),
+			range: InclusiveInterval {
+				start: range.start(),
+				end: range.end(),
+			},
+		};
+		overlapping.push_left(self.root.as_deref());
+
+		return overlapping;
+	}
+}
+
+impl<I, K, V> DiscreteIntervalMultiMap<I, K, V> {
+	/// Makes a new, empty [`DiscreteIntervalMultiMap`].
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::{
+	/// 	DiscreteIntervalMultiMap, InclusiveInterval,
+	/// };
+	///
+	/// let map: DiscreteIntervalMultiMap<
+	/// 	i8,
+	/// 	InclusiveInterval<i8>,
+	/// 	bool,
+	/// > = DiscreteIntervalMultiMap::new();
+	/// ```
+	pub fn new() -> Self {
+		DiscreteIntervalMultiMap {
+			root: None,
+			len: 0,
+			inserted: 0,
+		}
+	}
+	/// Returns the number of entries in the map.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	/// Returns `true` if the map contains no entries.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+}
+
+/// The iterator returned from
+/// [`DiscreteIntervalMultiMap::overlapping()`].
+struct Overlapping<'a, I, K, V> {
+	// the nodes whose right subtrees haven't been visited yet, the
+	// top of the stack being the next node in order
+	stack: Vec<&'a Node<Entry<K, V>, I>>,
+	range: InclusiveInterval<I>,
+}
+impl<'a, I, K, V> Overlapping<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	fn push_left(&mut self, mut tree: Option<&'a Node<Entry<K, V>, I>>) {
+		// a subtree can only contain overlapping ranges if some range in
+		// it ends at or after the start of the range
+		while let Some(node) =
+			tree.filter(|node| node.summary >= self.range.start)
+		{
+			self.stack.push(node);
+			tree = node.left.as_deref();
+		}
+	}
+}
+impl<'a, I, K, V> Iterator for Overlapping<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Item = &'a Entry<K, V>;
+	fn next(&mut self) -> Option<Self::Item> {
+		while let Some(node) = self.stack.pop() {
+			// every node after this one starts after the range too
+			if node.item.key.start() > self.range.end {
+				self.stack.clear();
+				return None;
+			}
+
+			self.push_left(node.right.as_deref());
+
+			if overlaps(node.item.key, self.range) {
+				return Some(&node.item);
+			}
+		}
+
+		return None;
+	}
+}
+
+/// The iterator returned from
+/// [`DiscreteIntervalMultiMap::contained_in()`].
+struct ContainedIn<'a, I, K, V> {
+	// the nodes starting within or after the range whose right
+	// subtrees haven't been visited yet, the top of the stack being
+	// the next node in order
+	stack: Vec<&'a Node<Entry<K, V>, I>>,
+	range: InclusiveInterval<I>,
+}
+impl<'a, I, K, V> ContainedIn<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	fn push_left(&mut self, mut tree: Option<&'a Node<Entry<K, V>, I>>) {
+		while let Some(node) = tree {
+			// a node starting before the range and everything to its
+			// left can't be contained in it
+			if node.item.key.start() >= self.range.start {
+				self.stack.push(node);
+				tree = node.left.as_deref();
+			} else {
+				tree = node.right.as_deref();
+			}
+		}
+	}
+}
+impl<'a, I, K, V> Iterator for ContainedIn<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Item = &'a Entry<K, V>;
+	fn next(&mut self) -> Option<Self::Item> {
+		while let Some(node) = self.stack.pop() {
+			// every node after this one starts after the range too
+			if node.item.key.start() > self.range.end {
+				self.stack.clear();
+				return None;
+			}
+
+			self.push_left(node.right.as_deref());
+
+			if node.item.key.end() <= self.range.end {
+				return Some(&node.item);
+			}
+		}
+
+		return None;
+	}
+}
+
+/// The draining iterator returned from
+/// [`DiscreteIntervalMultiMap::remove_overlapping()`].
+struct RemoveOverlapping<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	map: &'a mut DiscreteIntervalMultiMap<I, K, V>,
+	range: InclusiveInterval<I>,
+}
+impl<'a, I, K, V> Iterator for RemoveOverlapping<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Item = (K, V);
+	fn next(&mut self) -> Option<Self::Item> {
+		// the entries before it have already been removed so the next
+		// entry to remove is always the first overlapping one
+		let position =
+			self.map.overlapping_entries(self.range).next()?.position();
+
+		let entry = augmented_tree::remove(&mut self.map.root, &position);
+		let Entry { key, value, .. } = entry.unwrap();
+		self.map.len -= 1;
+
+		return Some((key, value));
+	}
+}
+impl<'a, I, K, V> Drop for RemoveOverlapping<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	fn drop(&mut self) {
+		self.for_each(drop);
+	}
+}
+
+// Tree Functions ==========================
+
+impl<I, K, V> Augmented<I> for Entry<K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	// entries with equal ranges are ordered by when they were inserted
+	type Position = (I, I, u64);
+
+	fn position(&self) -> Self::Position {
+		(self.key.start(), self.key.end(), self.seq)
+	}
+	/// Returns the largest range end in the subtree.
+	fn summarize(&self, left: Option<&I>, right: Option<&I>) -> I {
+		[left, right]
+			.into_iter()
+			.flatten()
+			.copied()
+			.fold(self.key.end(), I::max)
+	}
+}
+
+// Trait Impls ==========================
+
+impl<I, K, V> IntoIterator for DiscreteIntervalMultiMap<I, K, V> {
+	type Item = (K, V);
+	type IntoIter = alloc::vec::IntoIter<(K, V)>;
+	fn into_iter(self) -> Self::IntoIter {
+		let mut entries = Vec::with_capacity(self.len);
+		augmented_tree::drain_into(
+			self.root,
+			&mut entries,
+			&mut |Entry { key, value, .. }| (key, value),
+		);
+
+		return entries.into_iter();
+	}
+}
+
+impl<I, K, V> Default for DiscreteIntervalMultiMap<I, K, V> {
+	fn default() -> Self {
+		DiscreteIntervalMultiMap::new()
+	}
+}
+
+impl<I, K, V> Clone for DiscreteIntervalMultiMap<I, K, V>
+where
+	I: Clone,
+	K: Clone,
+	V: Clone,
+{
+	fn clone(&self) -> Self {
+		DiscreteIntervalMultiMap {
+			root: self.root.clone(),
+			len: self.len,
+			inserted: self.inserted,
+		}
+	}
+}
+
+// the shape of the tree depends on the order of insertion so maps are
+// compared by their entries instead
+impl<I, K, V> PartialEq for DiscreteIntervalMultiMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I> + PartialEq,
+	V: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.len == other.len && self.iter().eq(other.iter())
+	}
+}
+
+impl<I, K, V> Eq for DiscreteIntervalMultiMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I> + Eq,
+	V: Eq,
+{
+}
+
+impl<I, K, V> Debug for DiscreteIntervalMultiMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I> + Debug,
+	V: Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+impl<I, K, V> Serialize for DiscreteIntervalMultiMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I> + Serialize,
+	V: Serialize,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut seq = serializer.serialize_seq(Some(self.len()))?;
+		for (range_bounds, value) in self.iter() {
+			seq.serialize_element(&(range_bounds, value))?;
+		}
+		seq.end()
+	}
+}
+
+impl<'de, I, K, V> Deserialize<'de> for DiscreteIntervalMultiMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I> + Deserialize<'de>,
+	V: Deserialize<'de>,
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_seq(DiscreteIntervalMultiMapVisitor {
+			i: PhantomData,
+			k: PhantomData,
+			v: PhantomData,
+		})
+	}
+}
+
+struct DiscreteIntervalMultiMapVisitor<I, K, V> {
+	i: PhantomData<I>,
+	k: PhantomData<K>,
+	v: PhantomData<V>,
+}
+
+impl<'de, I, K, V> Visitor<'de> for DiscreteIntervalMultiMapVisitor<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I> + Deserialize<'de>,
+	V: Deserialize<'de>,
+{
+	type Value = DiscreteIntervalMultiMap<I, K, V>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("a DiscreteIntervalMultiMap")
+	}
+
+	fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let mut map = DiscreteIntervalMultiMap::new();
+		let mut index = 0;
+		while let Some((range, value)) = access.next_element()? {
+			// `I` isn't required to be `Debug` so the range itself can't
+			// be included
+			map.try_insert(range, value).map_err(|_| {
+				serde::de::Error::custom(format_args!(
+					"element {index} is an invalid range"
+				))
+			})?;
+			index += 1;
+		}
+		Ok(map)
+	}
+}
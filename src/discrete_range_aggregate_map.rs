@@ -0,0 +1,765 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+
+use crate::augmented_tree::{self, Augmented, Tree};
+use crate::discrete_range_map::{
+	check_valid_range, clip_range, invalid_range_panic, PointType, RangeType,
+};
+use crate::{
+	DiscreteRangeMap, InclusiveInterval, InsertError, InvalidRangeError,
+	OverlapError,
+};
+
+/// A type with an associative `combine` operation and an `identity`
+/// element, used as the aggregate of a [`DiscreteRangeAggregateMap`].
+///
+/// `combine` must be associative, and combining anything with
+/// `identity()` on either side must return it unchanged. `combine`
+/// need not be commutative: the left operand always comes from the
+/// earlier entries.
+pub trait Monoid {
+	/// Returns the aggregate of no entries.
+	fn identity() -> Self;
+	/// Combines the aggregate of some entries with the aggregate of the
+	/// entries directly after them.
+	fn combine(&self, other: &Self) -> Self;
+}
+
+/// A [`Monoid`] which can be made from a single entry of a
+/// [`DiscreteRangeAggregateMap`].
+pub trait Aggregate<K, V>: Monoid {
+	/// Returns the aggregate of a single entry.
+	///
+	/// When an entry only partially overlaps the range given to
+	/// [`DiscreteRangeAggregateMap::aggregate()`] this is called with
+	/// the overlapping part of its range, so an aggregate weighted by
+	/// the length of `range` counts only the covered points.
+	fn from_entry(range: K, value: &V) -> Self;
+}
+
+/// An ordered map of non-overlapping ranges to values, based on
+/// [`DiscreteRangeMap`], which can aggregate the values of any range in
+/// `O(log n)` time.
+///
+/// Alongside the map it keeps a balanced binary tree with the
+/// [`Aggregate`] of every entry, where every node also stores the
+/// combined aggregate of its subtree. The tree is updated by each
+/// method that modifies the map, which is why the map is only
+/// accessible mutably via the methods on this type.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is range over.
+///
+/// `K` is the generic type parameter for the range implementing type
+/// in the map.
+///
+/// `V` is the generic type parameter for the values associated with the
+/// keys in the map.
+///
+/// `A` is the generic type parameter for the [`Aggregate`] of the
+/// entries in the map.
+///
+/// # Examples
+/// ```
+/// use discrete_range_map::test_ranges::ie;
+/// use discrete_range_map::{
+/// 	Aggregate, DiscreteRangeAggregateMap, InclusiveInterval,
+/// 	Monoid,
+/// };
+///
+/// #[derive(Debug, PartialEq)]
+/// struct TotalCost(i32);
+///
+/// impl Monoid for TotalCost {
+/// 	fn identity() -> Self {
+/// 		TotalCost(0)
+/// 	}
+/// 	fn combine(&self, other: &Self) -> Self {
+/// 		TotalCost(self.0 + other.0)
+/// 	}
+/// }
+///
+/// // each value is the cost per point
+/// impl Aggregate<InclusiveInterval<i8>, i32> for TotalCost {
+/// 	fn from_entry(
+/// 		range: InclusiveInterval<i8>,
+/// 		value: &i32,
+/// 	) -> Self {
+/// 		TotalCost(i32::from(range.end - range.start + 1) * value)
+/// 	}
+/// }
+///
+/// let mut costs: DiscreteRangeAggregateMap<_, _, _, TotalCost> =
+/// 	DiscreteRangeAggregateMap::new();
+///
+/// costs.insert_strict(ie(0, 10), 1).unwrap();
+/// costs.insert_strict(ie(10, 20), 5).unwrap();
+///
+/// assert_eq!(costs.aggregate(ie(5, 15)), TotalCost(5 + 25));
+///
+/// costs.insert_overwrite(ie(12, 14), 0);
+///
+/// assert_eq!(costs.aggregate(ie(5, 15)), TotalCost(5 + 15));
+/// ```
+pub struct DiscreteRangeAggregateMap<I, K, V, A> {
+	inner: DiscreteRangeMap<I, K, V>,
+	// each node's summary is the combined aggregate of every entry in
+	// its subtree
+	index: Tree<Entry<I, A>, A>,
+}
+
+#[derive(Clone)]
+struct Entry<I, A> {
+	start: I,
+	aggregate: A,
+}
+
+impl<I, K, V, A> DiscreteRangeAggregateMap<I, K, V, A>
+where
+	I: PointType,
+	K: RangeType<I>,
+	A: Aggregate<K, V>,
+{
+	/// Returns the combined [`Aggregate`] of every entry in the map that
+	/// overlaps the given range, in ascending order, with the entries
+	/// that partially overlap the given range clipped to it.
+	///
+	/// This runs in `O(log n)` time.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{
+	/// 	Aggregate, DiscreteRangeAggregateMap, InclusiveInterval,
+	/// 	Monoid,
+	/// };
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// struct EntryCount(usize);
+	///
+	/// impl Monoid for EntryCount {
+	/// 	fn identity() -> Self {
+	/// 		EntryCount(0)
+	/// 	}
+	/// 	fn combine(&self, other: &Self) -> Self {
+	/// 		EntryCount(self.0 + other.0)
+	/// 	}
+	/// }
+	///
+	/// impl Aggregate<InclusiveInterval<i8>, bool> for EntryCount {
+	/// 	fn from_entry(_: InclusiveInterval<i8>, _: &bool) -> Self {
+	/// 		EntryCount(1)
+	/// 	}
+	/// }
+	///
+	/// let mut map: DiscreteRangeAggregateMap<_, _, _, EntryCount> =
+	/// 	DiscreteRangeAggregateMap::new();
+	///
+	/// map.insert_strict(ie(1, 4), false).unwrap();
+	/// map.insert_strict(ie(4, 8), true).unwrap();
+	/// map.insert_strict(ie(8, 100), false).unwrap();
+	///
+	/// assert_eq!(map.aggregate(ie(2, 6)), EntryCount(2));
+	/// assert_eq!(map.aggregate(ie(0, 120)), EntryCount(3));
+	/// assert_eq!(map.aggregate(ie(100, 120)), EntryCount(0));
+	/// ```
+	pub fn aggregate<Q>(&self, range: Q) -> A
+	where
+		Q: RangeType<I>,
+	{
+		invalid_range_panic(range);
+
+		let first = self.inner.get_entry_at_point(range.start()).ok();
+		let last = self.inner.get_entry_at_point(range.end()).ok();
+
+		let mut aggregate = A::identity();
+		let mut from = range.start();
+		let mut to = range.end();
+
+		if let Some((key, value)) = first {
+			if key.end() >= range.end() {
+				return A::from_entry(clip_range(*key, range), value);
+			}
+
+			aggregate = A::from_entry(clip_range(*key, range), value);
+			// the entry ends before the end of the range so up() can't fail
+			from = key.end().up().unwrap();
+		}
+		if let Some((key, _)) = last {
+			// the entry doesn't contain the start of the range so down()
+			// can't fail
+			to = key.start().down().unwrap();
+		}
+
+		// every entry starting in `from..=to` is entirely within the range
+		aggregate = fold(&self.index, Some(from), Some(to), aggregate);
+
+		if let Some((key, value)) = last {
+			aggregate = aggregate
+				.combine(&A::from_entry(clip_range(*key, range), value));
+		}
+
+		return aggregate;
+	}
+
+	/// Cuts a given range out of the map and returns an iterator of
+	/// the full or partial ranges that were cut.
+	///
+	/// Unlike [`DiscreteRangeMap::cut()`] the entries are cut straight
+	/// away and collected into a [`Vec`], rather than as the iterator is
+	/// advanced, as the aggregates must be updated before this returns.
+	///
+	/// See [`DiscreteRangeMap::cut()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn cut<Q>(&mut self, range: Q) -> impl Iterator<Item = (K, V)>
+	where
+		Q: RangeType<I>,
+		V: Clone,
+	{
+		invalid_range_panic(range);
+
+		self.update(range, |inner| inner.cut(range).collect::<Vec<_>>())
+			.into_iter()
+	}
+
+	/// Removes every entry in the map which overlaps the given range
+	/// and returns them in an iterator.
+	///
+	/// Unlike [`DiscreteRangeMap::remove_overlapping()`] the entries are
+	/// removed straight away and collected into a [`Vec`], rather than
+	/// as the iterator is advanced, as the aggregates must be updated
+	/// before this returns.
+	///
+	/// See [`DiscreteRangeMap::remove_overlapping()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn remove_overlapping<Q>(
+		&mut self,
+		range: Q,
+	) -> impl Iterator<Item = (K, V)>
+	where
+		Q: RangeType<I>,
+	{
+		invalid_range_panic(range);
+
+		self.update(range, |inner| {
+			inner.remove_overlapping(range).collect::<Vec<_>>()
+		})
+		.into_iter()
+	}
+
+	/// Adds a new entry to the map without modifying other entries.
+	///
+	/// See [`DiscreteRangeMap::insert_strict()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_strict(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>> {
+		invalid_range_panic(range);
+
+		self.update(range, |inner| inner.insert_strict(range, value))
+	}
+
+	/// Adds a new entry to the map and merges into other ranges in
+	/// the map which touch it.
+	///
+	/// See [`DiscreteRangeMap::insert_merge_touching()`] for more
+	/// details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_merge_touching(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>> {
+		invalid_range_panic(range);
+
+		self.update(range, |inner| inner.insert_merge_touching(range, value))
+	}
+
+	/// Adds a new entry to the map and merges into other ranges in
+	/// the map which touch it if the touching ranges' values are equal
+	/// to the value being inserted.
+	///
+	/// See [`DiscreteRangeMap::insert_merge_touching_if_values_equal()`]
+	/// for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_merge_touching_if_values_equal(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		V: Eq,
+	{
+		invalid_range_panic(range);
+
+		self.update(range, |inner| {
+			inner.insert_merge_touching_if_values_equal(range, value)
+		})
+	}
+
+	/// Adds a new entry to the map and merges into other ranges in
+	/// the map which overlap it.
+	///
+	/// See [`DiscreteRangeMap::insert_merge_overlapping()`] for more
+	/// details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_merge_overlapping(&mut self, range: K, value: V) -> K {
+		invalid_range_panic(range);
+
+		self.update(range, |inner| inner.insert_merge_overlapping(range, value))
+	}
+
+	/// Adds a new entry to the map and merges into other ranges in
+	/// the map which touch or overlap it.
+	///
+	/// See [`DiscreteRangeMap::insert_merge_touching_or_overlapping()`]
+	/// for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_merge_touching_or_overlapping(
+		&mut self,
+		range: K,
+		value: V,
+	) -> K {
+		invalid_range_panic(range);
+
+		self.update(range, |inner| {
+			inner.insert_merge_touching_or_overlapping(range, value)
+		})
+	}
+
+	/// Adds a new entry to the map and overwrites any other ranges
+	/// that overlap the new range.
+	///
+	/// See [`DiscreteRangeMap::insert_overwrite()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_overwrite(&mut self, range: K, value: V)
+	where
+		V: Clone,
+	{
+		invalid_range_panic(range);
+
+		self.update(range, |inner| inner.insert_overwrite(range, value))
+	}
+
+	/// Fallible version of [`DiscreteRangeAggregateMap::aggregate()`]
+	/// which returns an [`InvalidRangeError`] rather than panicking if
+	/// the given range is an invalid range.
+	pub fn try_aggregate<Q>(&self, range: Q) -> Result<A, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.aggregate(range))
+	}
+	/// Fallible version of [`DiscreteRangeAggregateMap::cut()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are cut.
+	pub fn try_cut<Q>(
+		&mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (K, V)>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+		V: Clone,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.cut(range))
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::remove_overlapping()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are removed.
+	pub fn try_remove_overlapping<Q>(
+		&mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (K, V)>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.remove_overlapping(range))
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::insert_strict()`].
+	///
+	/// See [`DiscreteRangeMap::try_insert_strict()`] for more details.
+	pub fn try_insert_strict(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), InsertError<I, K, V>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_strict(range, value)?)
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::insert_merge_touching()`].
+	///
+	/// See [`DiscreteRangeMap::try_insert_merge_touching()`] for more
+	/// details.
+	pub fn try_insert_merge_touching(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InsertError<I, K, V>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_touching(range, value)?)
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::insert_merge_touching_if_values_equal()`].
+	///
+	/// See
+	/// [`DiscreteRangeMap::try_insert_merge_touching_if_values_equal()`]
+	/// for more details.
+	pub fn try_insert_merge_touching_if_values_equal(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InsertError<I, K, V>>
+	where
+		V: Eq,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_touching_if_values_equal(range, value)?)
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::insert_merge_overlapping()`].
+	///
+	/// See [`DiscreteRangeMap::try_insert_merge_overlapping()`] for
+	/// more details.
+	pub fn try_insert_merge_overlapping(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_overlapping(range, value))
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::insert_merge_touching_or_overlapping()`].
+	///
+	/// See
+	/// [`DiscreteRangeMap::try_insert_merge_touching_or_overlapping()`]
+	/// for more details.
+	pub fn try_insert_merge_touching_or_overlapping(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_touching_or_overlapping(range, value))
+	}
+	/// Fallible version of
+	/// [`DiscreteRangeAggregateMap::insert_overwrite()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are
+	/// overwritten.
+	pub fn try_insert_overwrite(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), InvalidRangeError<I>>
+	where
+		V: Clone,
+	{
+		check_valid_range(range)?;
+
+		self.insert_overwrite(range, value);
+		Ok(())
+	}
+
+	/// Applies `f` to the map and updates the aggregates of the entries
+	/// that `f` could have changed, on the condition that `f` only
+	/// changes entries which overlap or touch the given range and only
+	/// adds entries within those entries and the given range.
+	fn update<Q, F, R>(&mut self, range: Q, f: F) -> R
+	where
+		Q: RangeType<I>,
+		F: FnOnce(&mut DiscreteRangeMap<I, K, V>) -> R,
+	{
+		let touching_or_overlapping = InclusiveInterval {
+			start: range.start().down().unwrap_or(range.start()),
+			end: range.end().up().unwrap_or(range.end()),
+		};
+
+		let mut changed = InclusiveInterval {
+			start: range.start(),
+			end: range.end(),
+		};
+		for (key, _) in self.inner.overlapping(touching_or_overlapping) {
+			augmented_tree::remove(&mut self.index, &key.start());
+
+			changed.start = changed.start.min(key.start());
+			changed.end = changed.end.max(key.end());
+		}
+
+		let result = f(&mut self.inner);
+
+		for (key, value) in self.inner.overlapping(changed) {
+			self.index = Some(augmented_tree::insert(
+				self.index.take(),
+				Entry {
+					start: key.start(),
+					aggregate: A::from_entry(*key, value),
+				},
+			));
+		}
+
+		return result;
+	}
+}
+
+impl<I, K, V, A> DiscreteRangeAggregateMap<I, K, V, A> {
+	/// Makes a new, empty [`DiscreteRangeAggregateMap`].
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::{
+	/// 	Aggregate, DiscreteRangeAggregateMap, InclusiveInterval,
+	/// 	Monoid,
+	/// };
+	///
+	/// struct Count(usize);
+	///
+	/// impl Monoid for Count {
+	/// 	fn identity() -> Self {
+	/// 		Count(0)
+	/// 	}
+	/// 	fn combine(&self, other: &Self) -> Self {
+	/// 		Count(self.0 + other.0)
+	/// 	}
+	/// }
+	///
+	/// impl Aggregate<InclusiveInterval<i8>, bool> for Count {
+	/// 	fn from_entry(_: InclusiveInterval<i8>, _: &bool) -> Self {
+	/// 		Count(1)
+	/// 	}
+	/// }
+	///
+	/// let map: DiscreteRangeAggregateMap<
+	/// 	i8,
+	/// 	InclusiveInterval<i8>,
+	/// 	bool,
+	/// 	Count,
+	/// > = DiscreteRangeAggregateMap::new();
+	/// ```
+	pub fn new() -> Self {
+		DiscreteRangeAggregateMap {
+			inner: DiscreteRangeMap::new(),
+			index: None,
+		}
+	}
+	/// Returns the number of entries in the map.
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+	/// Returns `true` if the map contains no entries.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+	/// Returns a reference to the underlying [`DiscreteRangeMap`].
+	pub fn as_map(&self) -> &DiscreteRangeMap<I, K, V> {
+		&self.inner
+	}
+	/// Returns the underlying [`DiscreteRangeMap`], discarding the
+	/// aggregates.
+	pub fn into_map(self) -> DiscreteRangeMap<I, K, V> {
+		self.inner
+	}
+}
+
+// Tree Functions ==========================
+
+impl<I, A> Augmented<A> for Entry<I, A>
+where
+	I: Ord + Copy,
+	A: Monoid,
+{
+	type Position = I;
+
+	fn position(&self) -> Self::Position {
+		self.start
+	}
+	/// Returns the combined aggregate of every entry in the subtree.
+	fn summarize(&self, left: Option<&A>, right: Option<&A>) -> A {
+		let left_and_entry = match left {
+			Some(left) => left.combine(&self.aggregate),
+			None => A::identity().combine(&self.aggregate),
+		};
+
+		match right {
+			Some(right) => left_and_entry.combine(right),
+			None => left_and_entry,
+		}
+	}
+}
+
+/// Combines `accumulator` with the aggregates of every entry in the tree
+/// starting within `from..=to` in order, where `None` is unbounded.
+fn fold<I, A>(
+	tree: &Tree<Entry<I, A>, A>,
+	from: Option<I>,
+	to: Option<I>,
+	accumulator: A,
+) -> A
+where
+	I: Ord + Copy,
+	A: Monoid,
+{
+	let Some(node) = tree.as_deref() else {
+		return accumulator;
+	};
+
+	if from.is_none() && to.is_none() {
+		return accumulator.combine(&node.summary);
+	}
+	if from.is_some_and(|from| node.item.start < from) {
+		return fold(&node.right, from, to, accumulator);
+	}
+	if to.is_some_and(|to| node.item.start > to) {
+		return fold(&node.left, from, to, accumulator);
+	}
+
+	// the node is within the bounds so its left subtree is bounded only
+	// by `from` and its right subtree only by `to`
+	let accumulator = fold(&node.left, from, None, accumulator);
+	let accumulator = accumulator.combine(&node.item.aggregate);
+
+	return fold(&node.right, None, to, accumulator);
+}
+
+// Trait Impls ==========================
+
+impl<I, K, V, A> Default for DiscreteRangeAggregateMap<I, K, V, A> {
+	fn default() -> Self {
+		DiscreteRangeAggregateMap::new()
+	}
+}
+
+impl<I, K, V, A> Clone for DiscreteRangeAggregateMap<I, K, V, A>
+where
+	I: Clone,
+	K: Clone,
+	V: Clone,
+	A: Clone,
+{
+	fn clone(&self) -> Self {
+		DiscreteRangeAggregateMap {
+			inner: self.inner.clone(),
+			index: self.index.clone(),
+		}
+	}
+}
+
+// the aggregates are derived from the entries so only the entries are
+// compared and printed
+impl<I, K, V, A> PartialEq for DiscreteRangeAggregateMap<I, K, V, A>
+where
+	I: PartialEq,
+	K: PartialEq,
+	V: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+}
+
+impl<I, K, V, A> Eq for DiscreteRangeAggregateMap<I, K, V, A>
+where
+	I: Eq,
+	K: Eq,
+	V: Eq,
+{
+}
+
+impl<I, K, V, A> Debug for DiscreteRangeAggregateMap<I, K, V, A>
+where
+	I: Debug,
+	K: Debug,
+	V: Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.inner.fmt(f)
+	}
+}
+
+impl<I, K, V, A> From<DiscreteRangeMap<I, K, V>>
+	for DiscreteRangeAggregateMap<I, K, V, A>
+where
+	I: PointType,
+	K: RangeType<I>,
+	A: Aggregate<K, V>,
+{
+	fn from(inner: DiscreteRangeMap<I, K, V>) -> Self {
+		let mut index = None;
+		for (key, value) in inner.iter() {
+			index = Some(augmented_tree::insert(
+				index,
+				Entry {
+					start: key.start(),
+					aggregate: A::from_entry(*key, value),
+				},
+			));
+		}
+
+		DiscreteRangeAggregateMap { inner, index }
+	}
+}
@@ -0,0 +1,376 @@
+use core::iter::once;
+
+use either::Either;
+use itertools::Itertools;
+
+use crate::discrete_range_map::{check_valid_range, PointType, RangeType};
+use crate::utils::join_touching;
+use crate::{DiscreteRangeMap, InclusiveInterval, InvalidRangeError};
+
+/// An ordered map of non-overlapping ranges to the number of times
+/// each range has been covered, based on [`DiscreteRangeMap`].
+///
+/// Unlike [`DiscreteRangeMap`], the ranges inserted into a
+/// [`DiscreteRangeCounter`] may overlap. Each range is split at the
+/// boundaries of the ranges it overlaps and the count of every covered
+/// sub-range is incremented. Touching ranges with equal counts are
+/// always merged so that each stored range is maximally-sized.
+///
+/// `I` is the generic type parameter for the [`Ord`] type the `K`
+/// type is range over.
+///
+/// `K` is the generic type parameter for the range implementing type
+/// in the counter.
+///
+/// Phrasing it another way: `I` is the point type and `K` is the range type.
+///
+/// # Examples
+/// ```
+/// use discrete_range_map::test_ranges::ie;
+/// use discrete_range_map::DiscreteRangeCounter;
+///
+/// let mut reservations = DiscreteRangeCounter::new();
+///
+/// reservations.insert(ie(1, 6));
+/// reservations.insert(ie(4, 10));
+///
+/// assert_eq!(reservations.count_at_point(2), 1);
+/// assert_eq!(reservations.count_at_point(5), 2);
+/// assert_eq!(reservations.count_at_point(20), 0);
+///
+/// assert_eq!(reservations.max_depth(ie(0, 100)), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscreteRangeCounter<I, K> {
+	inner: DiscreteRangeMap<I, K, usize>,
+}
+
+impl<I, K> DiscreteRangeCounter<I, K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Increments the count of every point in the given range.
+	///
+	/// Counts saturate rather than overflow, so a point covered
+	/// `usize::MAX` times or more has a count of `usize::MAX`.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeCounter;
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 4));
+	/// counter.insert(ie(2, 8));
+	/// counter.insert(ie(4, 8));
+	///
+	/// assert_eq!(
+	/// 	counter.iter().collect::<Vec<_>>(),
+	/// 	[(&ie(1, 2), 1), (&ie(2, 8), 2)]
+	/// );
+	/// ```
+	pub fn insert(&mut self, range: K) {
+		self.update(range, |count| {
+			Some(count.map_or(1, |count| count.saturating_add(1)))
+		});
+	}
+
+	/// Decrements the count of every point in the given range.
+	///
+	/// Points in the given range whose count is already zero are left
+	/// at zero.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeCounter;
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 8));
+	/// counter.insert(ie(4, 8));
+	///
+	/// counter.remove(ie(0, 6));
+	///
+	/// assert_eq!(
+	/// 	counter.iter().collect::<Vec<_>>(),
+	/// 	[(&ie(4, 6), 1), (&ie(6, 8), 2)]
+	/// );
+	/// ```
+	pub fn remove(&mut self, range: K) {
+		self.update(range, |count| {
+			count.filter(|count| *count > 1).map(|count| count - 1)
+		});
+	}
+
+	/// Returns the count at the given point.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeCounter;
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 8));
+	/// counter.insert(ie(4, 8));
+	///
+	/// assert_eq!(counter.count_at_point(0), 0);
+	/// assert_eq!(counter.count_at_point(1), 1);
+	/// assert_eq!(counter.count_at_point(4), 2);
+	/// ```
+	pub fn count_at_point(&self, point: I) -> usize {
+		self.inner.get_at_point(point).copied().unwrap_or(0)
+	}
+
+	/// Returns the highest count of any point in the given range, or
+	/// zero if no point in the given range has been covered.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeCounter;
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 8));
+	/// counter.insert(ie(4, 8));
+	///
+	/// assert_eq!(counter.max_depth(ie(0, 4)), 1);
+	/// assert_eq!(counter.max_depth(ie(0, 5)), 2);
+	/// assert_eq!(counter.max_depth(ie(10, 20)), 0);
+	/// ```
+	pub fn max_depth<Q>(&self, range: Q) -> usize
+	where
+		Q: RangeType<I>,
+	{
+		self.inner
+			.overlapping(range)
+			.map(|(_, count)| *count)
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Returns an iterator over every maximally-sized range in which
+	/// every point has a count of at least `n`, in ascending order.
+	///
+	/// Every point has a count of at least zero, so if `n` is zero then
+	/// a single range covering every point is returned.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{
+	/// 	DiscreteRangeCounter, InclusiveInterval,
+	/// };
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 8));
+	/// counter.insert(ie(4, 8));
+	/// counter.insert(ie(6, 10));
+	/// counter.insert(ie(20, 30));
+	///
+	/// assert_eq!(
+	/// 	counter.ranges_with_count_at_least(1).collect::<Vec<_>>(),
+	/// 	[ie(1, 10), ie(20, 30)]
+	/// );
+	/// assert_eq!(
+	/// 	counter.ranges_with_count_at_least(2).collect::<Vec<_>>(),
+	/// 	[ie(4, 8)]
+	/// );
+	/// assert_eq!(
+	/// 	counter.ranges_with_count_at_least(0).collect::<Vec<_>>(),
+	/// 	[InclusiveInterval {
+	/// 		start: i8::MIN,
+	/// 		end: i8::MAX,
+	/// 	}]
+	/// );
+	/// ```
+	pub fn ranges_with_count_at_least(
+		&self,
+		n: usize,
+	) -> impl Iterator<Item = K> + '_ {
+		if n == 0 {
+			return Either::Left(once(K::from(InclusiveInterval {
+				start: I::MIN,
+				end: I::MAX,
+			})));
+		}
+
+		Either::Right(
+			self.inner
+				.iter()
+				.filter(move |(_, count)| **count >= n)
+				.map(|(range, _)| InclusiveInterval {
+					start: range.start(),
+					end: range.end(),
+				})
+				.coalesce(join_touching)
+				.map(K::from),
+		)
+	}
+
+	/// Returns an iterator over every range in the counter along with
+	/// its count, in ascending order.
+	///
+	/// Ranges with a count of zero are not stored and so are not
+	/// yielded.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeCounter;
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 8));
+	/// counter.insert(ie(4, 6));
+	///
+	/// assert_eq!(
+	/// 	counter.iter().collect::<Vec<_>>(),
+	/// 	[(&ie(1, 4), 1), (&ie(4, 6), 2), (&ie(6, 8), 1)]
+	/// );
+	/// ```
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, usize)> {
+		self.inner.iter().map(|(range, count)| (range, *count))
+	}
+
+	/// Fallible version of [`DiscreteRangeCounter::insert()`] which
+	/// returns an [`InvalidRangeError`] rather than panicking if the
+	/// given range is an invalid range, in which case the counter is
+	/// not updated.
+	pub fn try_insert(&mut self, range: K) -> Result<(), InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		self.insert(range);
+		Ok(())
+	}
+	/// Fallible version of [`DiscreteRangeCounter::remove()`] which
+	/// returns an [`InvalidRangeError`] rather than panicking if the
+	/// given range is an invalid range, in which case the counter is
+	/// not updated.
+	pub fn try_remove(&mut self, range: K) -> Result<(), InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		self.remove(range);
+		Ok(())
+	}
+	/// Fallible version of [`DiscreteRangeCounter::max_depth()`] which
+	/// returns an [`InvalidRangeError`] rather than panicking if the
+	/// given range is an invalid range.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::{ie, ii};
+	/// use discrete_range_map::{
+	/// 	DiscreteRangeCounter, InvalidRangeError,
+	/// };
+	///
+	/// let mut counter = DiscreteRangeCounter::new();
+	///
+	/// counter.insert(ie(1, 8));
+	///
+	/// assert_eq!(counter.try_max_depth(ii(2, 4)), Ok(1));
+	/// assert_eq!(
+	/// 	counter.try_max_depth(ii(4, 2)),
+	/// 	Err(InvalidRangeError { start: 4, end: 2 })
+	/// );
+	/// ```
+	pub fn try_max_depth<Q>(
+		&self,
+		range: Q,
+	) -> Result<usize, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.max_depth(range))
+	}
+
+	/// Applies `f` to the count of every sub-range of the given range,
+	/// where `None` represents a count of zero, and re-merges touching
+	/// ranges with equal counts.
+	fn update<F>(&mut self, range: K, mut f: F)
+	where
+		F: FnMut(Option<usize>) -> Option<usize>,
+	{
+		self.inner.update_segments(
+			range,
+			|count| f(count.copied()),
+			|inner, range, count| {
+				// `range` was cut out of the map so it can't overlap
+				inner.insert_merge_touching_if_values_equal_unchecked(
+					range, count,
+				);
+			},
+		);
+	}
+}
+
+impl<I, K> DiscreteRangeCounter<I, K> {
+	/// Makes a new, empty [`DiscreteRangeCounter`].
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::{
+	/// 	DiscreteRangeCounter, InclusiveInterval,
+	/// };
+	///
+	/// let counter: DiscreteRangeCounter<i8, InclusiveInterval<i8>> =
+	/// 	DiscreteRangeCounter::new();
+	/// ```
+	pub fn new() -> Self {
+		DiscreteRangeCounter {
+			inner: DiscreteRangeMap::new(),
+		}
+	}
+	/// Returns the number of maximally-sized ranges with a non-zero
+	/// count in the counter.
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+	/// Returns `true` if no point in the counter has a non-zero count.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+	/// Returns a reference to the underlying [`DiscreteRangeMap`] of
+	/// ranges to their counts.
+	pub fn as_map(&self) -> &DiscreteRangeMap<I, K, usize> {
+		&self.inner
+	}
+}
+
+// Trait Impls ==========================
+
+impl<I, K> Default for DiscreteRangeCounter<I, K>
+where
+	I: PointType,
+{
+	fn default() -> Self {
+		DiscreteRangeCounter {
+			inner: DiscreteRangeMap::default(),
+		}
+	}
+}
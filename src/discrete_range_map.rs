@@ -19,11 +19,13 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 
 use alloc::vec::Vec;
 use core::cmp::Ordering;
+use core::error::Error;
 use core::fmt::{self, Debug};
 use core::iter::once;
 use core::marker::PhantomData;
 
 use btree_monstrousity::btree_map::{
+	Cursor as BTreeMapCursor, CursorMut as BTreeMapCursorMut,
 	IntoIter as BTreeMapIntoIter, SearchBoundCustom,
 };
 use btree_monstrousity::BTreeMap;
@@ -33,9 +35,11 @@ use serde::de::{SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::discrete_finite::DiscreteFinite;
+use crate::discrete_finite::{DiscreteFinite, DiscreteMeasure};
 use crate::interval::InclusiveInterval;
-use crate::utils::{cmp_point_with_range, cut_range, is_valid_range, overlaps};
+use crate::utils::{
+	cmp_point_with_range, cut_range, is_valid_range, overlaps, sweep,
+};
 
 /// An ordered map of non-overlapping ranges based on [`BTreeMap`].
 ///
@@ -85,8 +89,63 @@ pub struct DiscreteRangeMap<I, K, V> {
 
 /// An error type to represent a range overlapping another range when
 /// it should not have.
+///
+/// The rejected range and value are given back along with the ranges
+/// already in the map which the rejected range overlapped.
+#[derive(PartialEq, Debug)]
+pub struct OverlapError<K, V> {
+	/// The range that was rejected.
+	pub range: K,
+	/// The ranges already in the map that the rejected range
+	/// overlapped, in ascending order.
+	pub overlapping: Vec<K>,
+	/// The value that was rejected along with the range.
+	pub value: V,
+}
+
+/// An error type to represent an invalid range, one whose start is
+/// greater than its end, being given to a fallible function.
+///
+/// See [`Invalid
+/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+/// for more details.
+#[derive(PartialEq, Debug)]
+pub struct InvalidRangeError<I> {
+	pub start: I,
+	pub end: I,
+}
+
+/// An error type to represent a range that couldn't be shifted by `n`
+/// points because one of its ends would overflow past the minimum or
+/// maximum point.
+#[derive(PartialEq, Debug)]
+pub struct ShiftOverflowError<I> {
+	pub start: I,
+	pub end: I,
+	pub n: i128,
+}
+
+/// An error type for the fallible insertion functions which can fail
+/// either because the given range was invalid or because it overlapped
+/// another range when it should not have.
 #[derive(PartialEq, Debug)]
-pub struct OverlapError;
+pub enum InsertError<I, K, V> {
+	/// See [`InvalidRangeError`].
+	InvalidRange(InvalidRangeError<I>),
+	/// See [`OverlapError`].
+	Overlap(OverlapError<K, V>),
+}
+
+/// A segment of a range yielded by [`DiscreteRangeMap::segments()`],
+/// either part of an entry in the map or part of a maximally-sized gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<K, V> {
+	/// The part of an entry's range within the queried range along
+	/// with the entry's value.
+	Filled(K, V),
+	/// The part of a gap within the queried range.
+	Gap(K),
+}
 
 /// The marker trait for valid point types, a blanket implementation is provided for all types
 /// which implement this traits' super-traits so you shouln't need to implement this yourself.
@@ -238,6 +297,93 @@ where
 			.range_mut(start_comp, start_bound, end_comp, end_bound)
 	}
 
+	/// Returns an iterator over every entry in the map that overlaps
+	/// the given range in ascending order, with each entry's range
+	/// trimmed to the given range.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// let mut overlapping = map.overlapping_clipped(ie(2, 8));
+	///
+	/// assert_eq!(
+	/// 	overlapping.collect::<Vec<_>>(),
+	/// 	[(ie(2, 4), &false), (ie(4, 8), &true)]
+	/// );
+	/// ```
+	pub fn overlapping_clipped<Q>(
+		&self,
+		range: Q,
+	) -> impl DoubleEndedIterator<Item = (K, &V)>
+	where
+		Q: RangeType<I>,
+	{
+		self.overlapping(range)
+			.map(move |(key, value)| (clip_range(*key, range), value))
+	}
+
+	/// Returns a mutable iterator over every entry in the map that
+	/// overlaps the given range in ascending order, with each entry's
+	/// range trimmed to the given range.
+	///
+	/// The ranges in the map are not modified, so editing a value
+	/// edits it for the whole of its entry's range and not just the
+	/// trimmed part.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), 1),
+	/// 	(ie(4, 8), 2),
+	/// 	(ie(8, 100), 3),
+	/// ])
+	/// .unwrap();
+	///
+	/// // weight each value by how much of its range overlaps 2..8
+	/// for (range, value) in map.overlapping_clipped_mut(ie(2, 8)) {
+	/// 	*value *= range.end - range.start + 1;
+	/// }
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 2), (ie(4, 8), 8), (ie(8, 100), 3)]
+	/// );
+	/// ```
+	pub fn overlapping_clipped_mut<Q>(
+		&mut self,
+		range: Q,
+	) -> impl DoubleEndedIterator<Item = (K, &mut V)>
+	where
+		Q: RangeType<I>,
+	{
+		self.overlapping_mut(range)
+			.map(move |(key, value)| (clip_range(*key, range), value))
+	}
+
 	/// Returns a reference to the value corresponding to the range in
 	/// the map that overlaps the given point, if any.
 	///
@@ -261,6 +407,88 @@ where
 		self.get_entry_at_point(point).map(|(_, value)| value).ok()
 	}
 
+	/// Returns an iterator over the entries corresponding to the range
+	/// in the map that overlaps each of the given points, if any, in
+	/// the same order as the points.
+	///
+	/// While the points are in ascending order the lookups walk along
+	/// the map rather than each searching it from the root, so looking
+	/// up many sorted points is close to linear. Any point smaller than
+	/// the one before it, or too far beyond it, falls back to a search.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.get_at_points([0, 3, 4, 7, 101, 2]).collect::<Vec<_>>(),
+	/// 	[
+	/// 		None,
+	/// 		Some((&ie(1, 4), &false)),
+	/// 		Some((&ie(4, 8), &true)),
+	/// 		Some((&ie(4, 8), &true)),
+	/// 		None,
+	/// 		Some((&ie(1, 4), &false)),
+	/// 	]
+	/// );
+	/// ```
+	pub fn get_at_points<P>(
+		&self,
+		points: P,
+	) -> impl Iterator<Item = Option<(&K, &V)>>
+	where
+		P: IntoIterator<Item = I>,
+	{
+		// the cursor points to the first entry which doesn't end before
+		// the previous point
+		let mut previous: Option<(I, BTreeMapCursor<K, V>)> = None;
+
+		points.into_iter().map(move |point| {
+			let search = || {
+				self.inner.lower_bound(
+					overlapping_comp(point),
+					SearchBoundCustom::Included,
+				)
+			};
+			let ends_before_point = |cursor: &BTreeMapCursor<K, V>| {
+				cursor.key().is_some_and(|key| key.end() < point)
+			};
+
+			let cursor = match previous.take() {
+				Some((previous_point, mut cursor))
+					if previous_point <= point =>
+				{
+					// every entry before the cursor ends before the
+					// previous point so it's enough to step past the
+					// cursor's entry if it ends before this point too
+					if ends_before_point(&cursor) {
+						cursor.move_next();
+					}
+					if ends_before_point(&cursor) {
+						search()
+					} else {
+						cursor
+					}
+				}
+				_ => search(),
+			};
+
+			let entry =
+				cursor.key_value().filter(|(key, _)| key.start() <= point);
+			previous = Some((point, cursor));
+
+			return entry;
+		})
+	}
+
 	/// Returns a mutable reference to the value corresponding to the
 	/// range that overlaps the given point, if any.
 	///
@@ -333,6 +561,152 @@ where
 			.get_key_value(overlapping_comp(point))
 			.ok_or_else(|| K::from(self.get_gap_at_raw(point)))
 	}
+	/// Gets the given point's corresponding entry in the map for
+	/// in-place manipulation.
+	///
+	/// If a range in the map overlaps the given point then an
+	/// [`Entry::Occupied`] is returned, otherwise an [`Entry::Vacant`]
+	/// is returned containing the maximally-sized gap at the given
+	/// point, the same gap that
+	/// [`DiscreteRangeMap::get_entry_at_point()`] would return.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::discrete_range_map::Entry;
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), 1),
+	/// 	(ie(8, 100), 2),
+	/// ])
+	/// .unwrap();
+	///
+	/// match map.entry_at_point(2) {
+	/// 	Entry::Occupied(mut entry) => *entry.get_mut() += 10,
+	/// 	Entry::Vacant(_) => unreachable!(),
+	/// }
+	/// match map.entry_at_point(5) {
+	/// 	Entry::Occupied(_) => unreachable!(),
+	/// 	Entry::Vacant(entry) => {
+	/// 		assert_eq!(entry.gap(), &ie(4, 8));
+	/// 		entry.insert_strict(ie(5, 7), 3).unwrap();
+	/// 	}
+	/// }
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 11), (ie(5, 7), 3), (ie(8, 100), 2)]
+	/// );
+	/// ```
+	pub fn entry_at_point(&mut self, point: I) -> Entry<'_, I, K, V> {
+		let cursor = self.inner.upper_bound_mut(
+			overlapping_comp(point),
+			SearchBoundCustom::Included,
+		);
+
+		if cursor.key().is_some_and(|range| range.contains(point)) {
+			return Entry::Occupied(OccupiedEntry {
+				cursor,
+				phantom: PhantomData,
+			});
+		}
+
+		// the cursor is at the entry before the point so the point must
+		// be in the gap after it
+		let gap = gap_after(&cursor.as_cursor()).unwrap();
+
+		return Entry::Vacant(VacantEntry { cursor, point, gap });
+	}
+	/// Returns a [`Cursor`] positioned just before the entry or
+	/// maximally-sized gap that overlaps the given point.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::discrete_range_map::CursorItem;
+	/// use discrete_range_map::test_ranges::{ie, iu, ue};
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 6), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// let mut cursor = map.cursor_at_point(5);
+	///
+	/// assert_eq!(
+	/// 	cursor.next(),
+	/// 	Some(CursorItem::Entry(&ie(4, 6), &true))
+	/// );
+	/// assert_eq!(cursor.next(), Some(CursorItem::Gap(ie(6, 8))));
+	/// assert_eq!(
+	/// 	cursor.next(),
+	/// 	Some(CursorItem::Entry(&ie(8, 100), &false))
+	/// );
+	/// assert_eq!(cursor.next(), Some(CursorItem::Gap(iu(100))));
+	/// assert_eq!(cursor.next(), None);
+	///
+	/// let mut cursor = map.cursor_at_point(0);
+	///
+	/// assert_eq!(cursor.prev(), None);
+	/// assert_eq!(cursor.next(), Some(CursorItem::Gap(ue(1))));
+	/// ```
+	pub fn cursor_at_point(&self, point: I) -> Cursor<'_, I, K, V> {
+		let inner = self
+			.inner
+			.upper_bound(overlapping_comp(point), SearchBoundCustom::Excluded);
+
+		Cursor {
+			after_gap: starts_after_gap(&inner, point),
+			inner,
+			phantom: PhantomData,
+		}
+	}
+	/// Returns a [`CursorMut`] positioned just before the entry or
+	/// maximally-sized gap that overlaps the given point.
+	///
+	/// See [`DiscreteRangeMap::cursor_at_point()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::discrete_range_map::CursorItem;
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), 1),
+	/// 	(ie(4, 6), 2),
+	/// 	(ie(8, 100), 3),
+	/// ])
+	/// .unwrap();
+	///
+	/// let mut cursor = map.cursor_mut_at_point(2);
+	///
+	/// if let Some(CursorItem::Entry(_, value)) = cursor.next() {
+	/// 	*value += 10;
+	/// }
+	/// assert_eq!(cursor.remove_next(), Some((ie(4, 6), 2)));
+	/// assert_eq!(cursor.insert_strict(ie(5, 7), 4), Ok(()));
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 11), (ie(5, 7), 4), (ie(8, 100), 3)]
+	/// );
+	/// ```
+	pub fn cursor_mut_at_point(&mut self, point: I) -> CursorMut<'_, I, K, V> {
+		let inner = self.inner.upper_bound_mut(
+			overlapping_comp(point),
+			SearchBoundCustom::Excluded,
+		);
+
+		CursorMut {
+			after_gap: starts_after_gap(&inner.as_cursor(), point),
+			inner,
+			phantom: PhantomData,
+		}
+	}
 	fn get_gap_at_raw(&self, point: I) -> InclusiveInterval<I> {
 		let lower = self
 			.inner
@@ -354,6 +728,11 @@ where
 	/// Removes every entry in the map which overlaps the given range
 	/// and returns them in an iterator.
 	///
+	/// The entries are removed lazily as the iterator is advanced, if
+	/// the iterator is dropped before being fully consumed then the
+	/// remaining overlapping entries are removed at that point, similar
+	/// to [`Vec::drain()`](alloc::vec::Vec::drain).
+	///
 	/// # Panics
 	///
 	/// Panics if the given range is an invalid range. See [`Invalid
@@ -393,30 +772,107 @@ where
 	{
 		invalid_range_panic(range);
 
-		let mut result = Vec::new();
-
-		let mut leftmost_cursor = self.inner.lower_bound_mut(
+		let leftmost_cursor = self.inner.lower_bound_mut(
 			overlapping_comp(range.start()),
 			SearchBoundCustom::Included,
 		);
 
-		while leftmost_cursor
-			.key()
-			.is_some_and(|inner_range| overlaps(*inner_range, range))
-		{
-			result.push(leftmost_cursor.remove_current().unwrap());
-		}
+		return RemoveOverlapping {
+			cursor: leftmost_cursor,
+			range,
+			phantom: PhantomData,
+		};
+	}
+
+	/// Retains only the entries specified by the predicate.
+	///
+	/// In other words, removes every entry for which `f(&range, &mut
+	/// value)` returns `false`. The entries are visited in ascending
+	/// order.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// map.retain(|_, value| *value);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(4, 8), true)]
+	/// );
+	/// ```
+	pub fn retain<F>(&mut self, f: F)
+	where
+		F: FnMut(&K, &mut V) -> bool,
+	{
+		self.inner.retain(f);
+	}
 
-		return result.into_iter();
+	/// Removes every entry in the map for which the predicate returns
+	/// `true` and returns them in an iterator, in ascending order.
+	///
+	/// The predicate may mutate the value of every entry it is given
+	/// whether or not the entry is removed. Like
+	/// [`DiscreteRangeMap::remove_overlapping()`] the entries are
+	/// removed lazily as the iterator is advanced and any remaining
+	/// entries are tested against the predicate and removed when the
+	/// iterator is dropped.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// let extracted = map.extract_if(|_, value| !*value);
+	///
+	/// assert_eq!(
+	/// 	extracted.collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), false), (ie(8, 100), false)]
+	/// );
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(4, 8), true)]
+	/// );
+	/// ```
+	pub fn extract_if<'a, F>(
+		&'a mut self,
+		pred: F,
+	) -> impl Iterator<Item = (K, V)> + '_
+	where
+		F: FnMut(&K, &mut V) -> bool + 'a,
+	{
+		self.inner.drain_filter(pred)
 	}
 
 	/// Cuts a given range out of the map and returns an iterator of
 	/// the full or partial ranges that were cut.
 	///
+	/// Like [`DiscreteRangeMap::remove_overlapping()`] the entries are
+	/// removed lazily as the iterator is advanced and any remaining
+	/// entries are removed when the iterator is dropped.
+	///
 	/// `V` must implement `Clone` as if you try to cut out the center
 	/// of a range in the map it will split into two different entries
 	/// using `Clone`. Or if you partially cut a range then
-	/// `V` must be cloned to be returned in the iterator.
+	/// `V` must be cloned to be returned in the iterator. See
+	/// [`DiscreteRangeMap::cut_with()`] for a version that doesn't
+	/// require `V: Clone`.
 	///
 	/// # Panics
 	///
@@ -456,43 +912,103 @@ where
 		Q: RangeType<I> + 'a,
 		V: Clone,
 	{
-		invalid_range_panic(range);
-
-		let start_comp = overlapping_comp(range.start());
-		let end_comp = overlapping_comp(range.end());
+		self.cut_with(range, clone_split)
+	}
 
-		let left_overlapping = self
-			.inner
-			.get_key_value(start_comp)
-			.map(|(key, _)| key)
-			.copied();
-		let right_overlapping = self
-			.inner
-			.get_key_value(end_comp)
-			.map(|(key, _)| key)
-			.copied();
+	/// Cuts a given range out of the map and returns an iterator of
+	/// the full or partial ranges that were cut, using the given
+	/// `split` function rather than `Clone` whenever a range in the map
+	/// has to be split in two.
+	///
+	/// `split` is given the range that is being split along with its
+	/// value and must return the values for the lower and upper pieces
+	/// in that order. If you cut out the center of a range then `split`
+	/// is called twice, first on the whole range and then on the
+	/// remaining upper piece.
+	///
+	/// See [`DiscreteRangeMap::cut()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{DiscreteRangeMap, InclusiveInterval};
+	///
+	/// // each character of the string belongs to one point of the range
+	/// let mut base = DiscreteRangeMap::from_slice_strict([(
+	/// 	ie(0, 10),
+	/// 	String::from("abcdefghij"),
+	/// )])
+	/// .unwrap();
+	///
+	/// let split_string = |range: &InclusiveInterval<i8>,
+	///                     mut value: String| {
+	/// 	let upper = value.split_off(5 - range.start as usize);
+	/// 	(value, upper)
+	/// };
+	///
+	/// assert_eq!(
+	/// 	base.cut_with(ie(5, 100), split_string).collect::<Vec<_>>(),
+	/// 	[(ie(5, 10), String::from("fghij"))]
+	/// );
+	/// assert_eq!(
+	/// 	base.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(0, 5), String::from("abcde"))]
+	/// );
+	/// ```
+	pub fn cut_with<'a, Q, F>(
+		&'a mut self,
+		range: Q,
+		split: F,
+	) -> impl Iterator<Item = (K, V)> + '_
+	where
+		Q: RangeType<I> + 'a,
+		F: FnMut(&K, V) -> (V, V) + 'a,
+	{
+		invalid_range_panic(range);
+
+		let start_comp = overlapping_comp(range.start());
+		let end_comp = overlapping_comp(range.end());
+
+		let left_overlapping = self
+			.inner
+			.get_key_value(start_comp)
+			.map(|(key, _)| key)
+			.copied();
+		let right_overlapping = self
+			.inner
+			.get_key_value(end_comp)
+			.map(|(key, _)| key)
+			.copied();
 
 		if let Some(left) = left_overlapping
 			&& let Some(right) = right_overlapping
 			&& left.start() == right.start()
 		{
-			Either::Left(self.cut_single_overlapping(range, left))
+			Either::Left(self.cut_single_overlapping(range, left, split))
 		} else {
 			Either::Right(self.cut_non_single_overlapping(
 				range,
 				left_overlapping,
 				right_overlapping,
+				split,
 			))
 		}
 	}
-	fn cut_single_overlapping<Q>(
+	fn cut_single_overlapping<Q, F>(
 		&mut self,
 		range: Q,
 		single_overlapping_range: K,
+		mut split: F,
 	) -> impl Iterator<Item = (K, V)>
 	where
 		Q: RangeType<I>,
-		V: Clone,
+		F: FnMut(&K, V) -> (V, V),
 	{
 		invalid_range_panic(range);
 
@@ -501,26 +1017,39 @@ where
 		let returning_before_cut = cut_result.before_cut.map(K::from);
 		let returning_after_cut = cut_result.after_cut.map(K::from);
 
-		let value = self.inner.remove(overlapping_comp(range.start())).unwrap();
+		let mut value =
+			self.inner.remove(overlapping_comp(range.start())).unwrap();
+		let mut remaining_range = single_overlapping_range;
 
 		if let Some(before) = returning_before_cut {
-			self.insert_unchecked(before, value.clone());
+			let (before_value, upper_value) = split(&remaining_range, value);
+			self.insert_unchecked(before, before_value);
+
+			value = upper_value;
+			remaining_range = K::from(InclusiveInterval {
+				start: range.start(),
+				end: remaining_range.end(),
+			});
 		}
 		if let Some(after) = returning_after_cut {
-			self.insert_unchecked(after, value.clone());
+			let (lower_value, after_value) = split(&remaining_range, value);
+			self.insert_unchecked(after, after_value);
+
+			value = lower_value;
 		}
 
 		once((cut_result.inside_cut.map(K::from).unwrap(), value))
 	}
-	fn cut_non_single_overlapping<'a, Q>(
+	fn cut_non_single_overlapping<'a, Q, F>(
 		&'a mut self,
 		range: Q,
 		left_overlapping: Option<K>,
 		right_overlapping: Option<K>,
+		mut split: F,
 	) -> impl Iterator<Item = (K, V)> + '_
 	where
 		Q: RangeType<I> + 'a,
-		V: Clone,
+		F: FnMut(&K, V) -> (V, V) + 'a,
 	{
 		invalid_range_panic(range);
 
@@ -547,20 +1076,23 @@ where
 			None => (None, None),
 		};
 
-		let before_value = self.inner.remove(overlapping_comp(range.start()));
-		let after_value = self.inner.remove(overlapping_comp(range.end()));
+		let mut before_value =
+			self.inner.remove(overlapping_comp(range.start()));
+		let mut after_value = self.inner.remove(overlapping_comp(range.end()));
 
 		if let Some(returning_before_cut) = returning_before_cut {
-			self.insert_unchecked(
-				returning_before_cut,
-				before_value.as_ref().cloned().unwrap(),
-			);
+			let (returning_value, keeping_value) =
+				split(&left_overlapping.unwrap(), before_value.unwrap());
+			self.insert_unchecked(returning_before_cut, returning_value);
+
+			before_value = Some(keeping_value);
 		}
 		if let Some(returning_after_cut) = returning_after_cut {
-			self.insert_unchecked(
-				returning_after_cut,
-				after_value.as_ref().cloned().unwrap(),
-			);
+			let (keeping_value, returning_value) =
+				split(&right_overlapping.unwrap(), after_value.unwrap());
+			self.insert_unchecked(returning_after_cut, returning_value);
+
+			after_value = Some(keeping_value);
 		}
 
 		let keeping_before_entry = keeping_before
@@ -570,15 +1102,7 @@ where
 
 		return keeping_before_entry
 			.into_iter()
-			.chain(self.remove_overlapping(range).map(|(key, value)| {
-				(
-					K::from(InclusiveInterval {
-						start: key.start(),
-						end: key.end(),
-					}),
-					value,
-				)
-			}))
+			.chain(self.remove_overlapping(range))
 			.chain(keeping_after_entry);
 	}
 
@@ -676,6 +1200,66 @@ where
 			.chain(trimmed_end_gap.map(K::from));
 	}
 
+	/// Returns a double-ended iterator over the entries and
+	/// maximally-sized gaps that together exactly cover the given
+	/// range, in ascending order.
+	///
+	/// The ranges of the first and last segments are trimmed to the
+	/// given range, so entries are yielded with their ranges trimmed
+	/// the same way as [`DiscreteRangeMap::cut()`] would return them.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::discrete_range_map::Segment;
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 3), false),
+	/// 	(ie(5, 7), true),
+	/// 	(ie(9, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.segments(ie(2, 10)).collect::<Vec<_>>(),
+	/// 	[
+	/// 		Segment::Filled(ie(2, 3), &false),
+	/// 		Segment::Gap(ie(3, 5)),
+	/// 		Segment::Filled(ie(5, 7), &true),
+	/// 		Segment::Gap(ie(7, 9)),
+	/// 		Segment::Filled(ie(9, 10), &false),
+	/// 	]
+	/// );
+	/// assert_eq!(
+	/// 	map.segments(ie(3, 6)).rev().collect::<Vec<_>>(),
+	/// 	[Segment::Filled(ie(5, 6), &true), Segment::Gap(ie(3, 5))]
+	/// );
+	/// ```
+	pub fn segments<Q>(
+		&self,
+		range: Q,
+	) -> impl DoubleEndedIterator<Item = Segment<K, &V>>
+	where
+		Q: RangeType<I>,
+	{
+		Segments {
+			overlapping: self.overlapping(range),
+			front: None,
+			back: None,
+			remaining: Some(InclusiveInterval {
+				start: range.start(),
+				end: range.end(),
+			}),
+		}
+	}
+
 	/// Returns `true` if the map covers every point in the given
 	/// range, and `false` if it does not.
 	///
@@ -711,6 +1295,102 @@ where
 		self.gaps(range).next().is_none()
 	}
 
+	/// Returns the number of points covered by the map, or `None` if
+	/// there are `2^128` of them.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{DiscreteRangeMap, InclusiveInterval};
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 3), false),
+	/// 	(ie(5, 8), true),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.covered_len(), Some(5));
+	///
+	/// let full = DiscreteRangeMap::from_slice_strict([(
+	/// 	InclusiveInterval {
+	/// 		start: i128::MIN,
+	/// 		end: i128::MAX,
+	/// 	},
+	/// 	(),
+	/// )])
+	/// .unwrap();
+	///
+	/// assert_eq!(full.covered_len(), None);
+	/// ```
+	pub fn covered_len(&self) -> Option<u128>
+	where
+		I: DiscreteMeasure,
+	{
+		sum_lens(self.iter().map(|(key, _)| *key))
+	}
+
+	/// Returns the number of points in the given range that are
+	/// covered by the map, or `None` if there are `2^128` of them.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 3), false),
+	/// 	(ie(5, 8), true),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.covered_len_within(ie(2, 6)), Some(2));
+	/// assert_eq!(map.covered_len_within(ie(3, 5)), Some(0));
+	/// ```
+	pub fn covered_len_within<Q>(&self, range: Q) -> Option<u128>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		sum_lens(self.overlapping_clipped(range).map(|(key, _)| key))
+	}
+
+	/// Returns the number of points in the given range that are not
+	/// covered by the map, or `None` if there are `2^128` of them.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 3), false),
+	/// 	(ie(5, 8), true),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.gaps_len(ie(2, 6)), Some(2));
+	/// assert_eq!(map.gaps_len(ie(5, 8)), Some(0));
+	/// ```
+	pub fn gaps_len<Q>(&self, range: Q) -> Option<u128>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		sum_lens(self.gaps(range))
+	}
+
 	/// Adds a new entry to the map without modifying other entries.
 	///
 	/// If the given range overlaps one or more ranges already in the
@@ -731,27 +1411,61 @@ where
 	/// let mut map = DiscreteRangeMap::new();
 	///
 	/// assert_eq!(map.insert_strict(ie(5, 10), 9), Ok(()));
-	/// assert_eq!(map.insert_strict(ie(5, 10), 2), Err(OverlapError));
+	/// assert_eq!(
+	/// 	map.insert_strict(ie(5, 10), 2),
+	/// 	Err(OverlapError {
+	/// 		range: ie(5, 10),
+	/// 		overlapping: vec![ie(5, 10)],
+	/// 		value: 2,
+	/// 	})
+	/// );
 	/// assert_eq!(map.len(), 1);
 	/// ```
 	pub fn insert_strict(
 		&mut self,
 		range: K,
 		value: V,
-	) -> Result<(), OverlapError> {
+	) -> Result<(), OverlapError<K, V>> {
 		invalid_range_panic(range);
 
 		if self.overlaps(range) {
-			return Err(OverlapError);
+			return Err(self.overlap_error(range, value));
 		}
 
 		self.insert_unchecked(range, value);
 
 		return Ok(());
 	}
+	fn overlap_error(&self, range: K, value: V) -> OverlapError<K, V> {
+		OverlapError {
+			range,
+			overlapping: self.overlapping(range).map(|(key, _)| *key).collect(),
+			value,
+		}
+	}
 	fn insert_unchecked(&mut self, range: K, value: V) {
 		self.inner.insert(range, value, double_comp());
 	}
+	/// Builds a map from entries that are already sorted and
+	/// non-overlapping by appending each one onto the end without
+	/// searching the tree.
+	pub(crate) fn from_sorted_iter_unchecked(
+		iter: impl Iterator<Item = (K, V)>,
+	) -> DiscreteRangeMap<I, K, V> {
+		let mut map = DiscreteRangeMap::new();
+
+		// inserting before the "ghost" non-element appends to the end
+		// of the map and leaves the cursor on the "ghost"
+		let mut cursor = map.inner.upper_bound_mut(
+			|_: &K| Ordering::Equal,
+			SearchBoundCustom::AllExcluded,
+		);
+		for (range, value) in iter {
+			cursor.insert_before(range, value);
+		}
+
+		return map;
+	}
 
 	fn insert_merge_with_comps<G1, G2, R1, R2>(
 		&mut self,
@@ -840,7 +1554,11 @@ where
 	/// // Overlapping
 	/// assert_eq!(
 	/// 	map.insert_merge_touching(ie(4, 8), false),
-	/// 	Err(OverlapError),
+	/// 	Err(OverlapError {
+	/// 		range: ie(4, 8),
+	/// 		overlapping: vec![ie(1, 8)],
+	/// 		value: false,
+	/// 	}),
 	/// );
 	///
 	/// // Neither Touching or Overlapping
@@ -858,14 +1576,17 @@ where
 		&mut self,
 		range: K,
 		value: V,
-	) -> Result<K, OverlapError> {
+	) -> Result<K, OverlapError<K, V>> {
 		invalid_range_panic(range);
 
 		if self.overlaps(range) {
-			return Err(OverlapError);
+			return Err(self.overlap_error(range, value));
 		}
 
-		Ok(self.insert_merge_with_comps(
+		Ok(self.insert_merge_touching_unchecked(range, value))
+	}
+	fn insert_merge_touching_unchecked(&mut self, range: K, value: V) -> K {
+		self.insert_merge_with_comps(
 			range,
 			value,
 			|selfy, _| {
@@ -888,7 +1609,7 @@ where
 			|selfy, _| {
 				selfy.inner.remove(touching_end_comp(range.end()));
 			},
-		))
+		)
 	}
 
 	/// Adds a new entry to the map and merges into other ranges in
@@ -928,7 +1649,11 @@ where
 	/// // Overlapping
 	/// assert_eq!(
 	/// 	map.insert_merge_touching_if_values_equal(ie(4, 8), false),
-	/// 	Err(OverlapError),
+	/// 	Err(OverlapError {
+	/// 		range: ie(4, 8),
+	/// 		overlapping: vec![ie(4, 8)],
+	/// 		value: false,
+	/// 	}),
 	/// );
 	///
 	/// // Neither Touching or Overlapping
@@ -946,16 +1671,26 @@ where
 		&mut self,
 		range: K,
 		value: V,
-	) -> Result<K, OverlapError>
+	) -> Result<K, OverlapError<K, V>>
 	where
 		V: Eq,
 	{
 		invalid_range_panic(range);
 
 		if self.overlaps(range) {
-			return Err(OverlapError);
+			return Err(self.overlap_error(range, value));
 		}
 
+		Ok(self.insert_merge_touching_if_values_equal_unchecked(range, value))
+	}
+	pub(crate) fn insert_merge_touching_if_values_equal_unchecked(
+		&mut self,
+		range: K,
+		value: V,
+	) -> K
+	where
+		V: Eq,
+	{
 		let get_start = |selfy: &Self, value: &V| {
 			selfy
 				.inner
@@ -977,7 +1712,7 @@ where
 				.copied()
 		};
 
-		Ok(self.insert_merge_with_comps(
+		self.insert_merge_with_comps(
 			range,
 			value,
 			get_start,
@@ -992,7 +1727,7 @@ where
 					selfy.inner.remove(touching_end_comp(range.end()));
 				}
 			},
-		))
+		)
 	}
 
 	/// Adds a new entry to the map and merges into other ranges in
@@ -1012,7 +1747,7 @@ where
 	/// # Examples
 	/// ```
 	/// use discrete_range_map::test_ranges::ie;
-	/// use discrete_range_map::{DiscreteRangeMap, OverlapError};
+	/// use discrete_range_map::DiscreteRangeMap;
 	///
 	/// let mut map = DiscreteRangeMap::from_slice_strict([
 	/// 	(ie(1, 4), false),
@@ -1085,7 +1820,7 @@ where
 	/// # Examples
 	/// ```
 	/// use discrete_range_map::test_ranges::ie;
-	/// use discrete_range_map::{DiscreteRangeMap, OverlapError};
+	/// use discrete_range_map::DiscreteRangeMap;
 	///
 	/// let mut map = DiscreteRangeMap::from_slice_strict([
 	/// 	(ie(1, 4), false),
@@ -1190,18 +1925,17 @@ where
 	where
 		V: Clone,
 	{
-		invalid_range_panic(range);
-
-		let _ = self.cut(range);
-		self.insert_unchecked(range, value);
+		self.insert_overwrite_with(range, value, clone_split);
 	}
 
-	/// Allocates a `DiscreteRangeMap` and moves the given entries from
-	/// the given slice into the map using
-	/// [`DiscreteRangeMap::insert_strict()`].
+	/// Adds a new entry to the map and overwrites any other ranges
+	/// that overlap the new range, using the given `split` function
+	/// rather than `Clone` whenever a range in the map has to be split
+	/// in two.
 	///
-	/// May return an `Err` while inserting. See
-	/// [`DiscreteRangeMap::insert_strict()`] for details.
+	/// This is equivalent to using [`DiscreteRangeMap::cut_with()`]
+	/// followed by [`DiscreteRangeMap::insert_strict()`]. See
+	/// [`DiscreteRangeMap::cut_with()`] for how `split` is called.
 	///
 	/// # Panics
 	///
@@ -1214,28 +1948,49 @@ where
 	/// use discrete_range_map::test_ranges::ie;
 	/// use discrete_range_map::DiscreteRangeMap;
 	///
-	/// let map = DiscreteRangeMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
-	/// ])
+	/// // give the lower piece a new value rather than cloning the old one
+	/// let mut map = DiscreteRangeMap::from_slice_strict([(
+	/// 	ie(2, 8),
+	/// 	Box::new(1),
+	/// )])
 	/// .unwrap();
+	///
+	/// map.insert_overwrite_with(ie(4, 6), Box::new(2), |_, value| {
+	/// 	(Box::new(*value + 10), value)
+	/// });
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(ie(2, 4), Box::new(11)),
+	/// 		(ie(4, 6), Box::new(2)),
+	/// 		(ie(6, 8), Box::new(1))
+	/// 	]
+	/// );
 	/// ```
-	pub fn from_slice_strict<const N: usize>(
-		slice: [(K, V); N],
-	) -> Result<DiscreteRangeMap<I, K, V>, OverlapError> {
-		let mut map = DiscreteRangeMap::new();
-		for (range, value) in slice {
-			map.insert_strict(range, value)?;
-		}
-		return Ok(map);
+	pub fn insert_overwrite_with<F>(&mut self, range: K, value: V, split: F)
+	where
+		F: FnMut(&K, V) -> (V, V),
+	{
+		invalid_range_panic(range);
+
+		let _ = self.cut_with(range, split);
+		self.insert_unchecked(range, value);
 	}
 
-	/// Collects a `DiscreteRangeMap` from an iterator of (range,
-	/// value) tuples using [`DiscreteRangeMap::insert_strict()`].
+	/// Adds a new entry to the map, combining its value with the values
+	/// of any ranges it overlaps.
 	///
-	/// May return an `Err` while inserting. See
-	/// [`DiscreteRangeMap::insert_strict()`] for details.
+	/// The portions of the map's ranges that overlap the new range are
+	/// given the value `combine(old_value, value)` and any gaps within
+	/// the new range are given a clone of `value`. Like
+	/// [`DiscreteRangeMap::cut()`], ranges that extend past either end
+	/// of the new range are split at the ends with their outside
+	/// portions keeping their old values. Hence the same `V: Clone`
+	/// trait bound applies.
+	///
+	/// See [`DiscreteRangeMap::insert_combine_coalesce()`] for a version
+	/// that merges touching ranges with equal values afterwards.
 	///
 	/// # Panics
 	///
@@ -1248,32 +2003,1481 @@ where
 	/// use discrete_range_map::test_ranges::ie;
 	/// use discrete_range_map::DiscreteRangeMap;
 	///
-	/// let slice =
-	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(8, 100), false)];
+	/// const READ: u8 = 0b01;
+	/// const WRITE: u8 = 0b10;
 	///
-	/// let map: DiscreteRangeMap<_, _, _> =
-	/// 	DiscreteRangeMap::from_iter_strict(
-	/// 		slice.into_iter().filter(|(range, _)| range.start > 2),
-	/// 	)
-	/// 	.unwrap();
-	/// ```
-	pub fn from_iter_strict(
-		iter: impl Iterator<Item = (K, V)>,
-	) -> Result<DiscreteRangeMap<I, K, V>, OverlapError> {
-		let mut map = DiscreteRangeMap::new();
-		for (range, value) in iter {
-			map.insert_strict(range, value)?;
-		}
-		return Ok(map);
-	}
-}
-
-impl<I, K, V> DiscreteRangeMap<I, K, V> {
-	/// Makes a new, empty `DiscreteRangeMap`.
+	/// let mut permissions =
+	/// 	DiscreteRangeMap::from_slice_strict([(ie(2, 6), READ)])
+	/// 		.unwrap();
 	///
-	/// # Examples
-	/// ```
-	/// use discrete_range_map::{DiscreteRangeMap, InclusiveInterval};
+	/// permissions
+	/// 	.insert_combine(ie(4, 10), WRITE, |old, new| old | new);
+	///
+	/// assert_eq!(
+	/// 	permissions.into_iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(ie(2, 4), READ),
+	/// 		(ie(4, 6), READ | WRITE),
+	/// 		(ie(6, 10), WRITE)
+	/// 	]
+	/// );
+	/// ```
+	pub fn insert_combine<F>(&mut self, range: K, value: V, mut combine: F)
+	where
+		F: FnMut(&V, &V) -> V,
+		V: Clone,
+	{
+		self.update_segments(
+			range,
+			|old_value| combined(old_value, &value, &mut combine),
+			DiscreteRangeMap::insert_unchecked,
+		);
+	}
+
+	/// Adds a new entry to the map, combining its value with the values
+	/// of any ranges it overlaps, and merges touching ranges with equal
+	/// values.
+	///
+	/// Ranges outside of the new range are only merged if they touch
+	/// one of the combined ranges.
+	///
+	/// See [`DiscreteRangeMap::insert_combine()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// const READ: u8 = 0b01;
+	/// const WRITE: u8 = 0b10;
+	///
+	/// let mut permissions = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(2, 6), READ),
+	/// 	(ie(6, 8), READ | WRITE),
+	/// ])
+	/// .unwrap();
+	///
+	/// permissions.insert_combine_coalesce(
+	/// 	ie(4, 6),
+	/// 	WRITE,
+	/// 	|old, new| old | new,
+	/// );
+	///
+	/// assert_eq!(
+	/// 	permissions.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(2, 4), READ), (ie(4, 8), READ | WRITE)]
+	/// );
+	/// ```
+	pub fn insert_combine_coalesce<F>(
+		&mut self,
+		range: K,
+		value: V,
+		mut combine: F,
+	) where
+		F: FnMut(&V, &V) -> V,
+		V: Clone + Eq,
+	{
+		self.update_segments(
+			range,
+			|old_value| combined(old_value, &value, &mut combine),
+			|map, range, value| {
+				map.insert_merge_touching_if_values_equal_unchecked(
+					range, value,
+				);
+			},
+		);
+	}
+
+	/// Replaces every segment of the given range with the value `f`
+	/// returns for the segment's old value, or for `None` if the
+	/// segment is a gap, leaving a gap where `f` returns `None`.
+	///
+	/// Entries extending past either end of the range are split like
+	/// [`DiscreteRangeMap::cut()`] does and the new entries are added
+	/// in ascending order with `insert`.
+	pub(crate) fn update_segments<F, G>(
+		&mut self,
+		range: K,
+		mut f: F,
+		mut insert: G,
+	) where
+		F: FnMut(Option<&V>) -> Option<V>,
+		G: FnMut(&mut Self, K, V),
+		V: Clone,
+	{
+		invalid_range_panic(range);
+
+		let updated = self
+			.segments(range)
+			.filter_map(|segment| match segment {
+				Segment::Filled(range, value) => Some((range, f(Some(value))?)),
+				Segment::Gap(range) => Some((range, f(None)?)),
+			})
+			.collect::<Vec<_>>();
+
+		let _ = self.cut(range);
+
+		for (range, value) in updated {
+			insert(self, range, value);
+		}
+	}
+
+	/// Overlays `other` on top of `self` and returns the result as a new
+	/// map, combining the values of overlapping portions with the given
+	/// `combine` function.
+	///
+	/// Every range is split at each point where a range of either map
+	/// starts or ends. Portions covered by both maps are given the value
+	/// `combine(self_value, other_value)` whereas portions covered by
+	/// only one of the maps keep a clone of that map's value.
+	///
+	/// This walks both maps in order at the same time and so runs in
+	/// linear time over the ranges of both maps.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let defaults = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(0, 10), 1),
+	/// 	(ie(20, 30), 2),
+	/// ])
+	/// .unwrap();
+	/// let overrides =
+	/// 	DiscreteRangeMap::from_slice_strict([(ie(5, 25), 10)])
+	/// 		.unwrap();
+	///
+	/// let merged = defaults.merge_with(&overrides, |a, b| a + b);
+	///
+	/// assert_eq!(
+	/// 	merged.into_iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(ie(0, 5), 1),
+	/// 		(ie(5, 10), 11),
+	/// 		(ie(10, 20), 10),
+	/// 		(ie(20, 25), 12),
+	/// 		(ie(25, 30), 2),
+	/// 	]
+	/// );
+	/// ```
+	pub fn merge_with<F>(
+		&self,
+		other: &Self,
+		combine: F,
+	) -> DiscreteRangeMap<I, K, V>
+	where
+		F: Fn(&V, &V) -> V,
+		V: Clone,
+	{
+		let segments = sweep(
+			self.iter().map(|(key, value)| (*key, value)),
+			other.iter().map(|(key, value)| (*key, value)),
+		)
+		.map(|(range, self_value, other_value)| {
+			let value = match (self_value, other_value) {
+				(Some(self_value), Some(other_value)) => {
+					combine(self_value, other_value)
+				}
+				(Some(value), None) | (None, Some(value)) => value.clone(),
+				(None, None) => unreachable!(),
+			};
+
+			(K::from(range), value)
+		});
+
+		DiscreteRangeMap::from_sorted_iter_unchecked(segments)
+	}
+
+	/// Makes a new map with the same ranges as `self` and the values
+	/// given by applying `f` to each entry, consuming `self`.
+	///
+	/// See [`DiscreteRangeMap::map_values_coalesce()`] for a version
+	/// that merges touching ranges whose new values are equal.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), 1),
+	/// 	(ie(4, 8), 2),
+	/// 	(ie(8, 100), 3),
+	/// ])
+	/// .unwrap();
+	///
+	/// let map = map.map_values(|_, value| value * 10);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), 10), (ie(4, 8), 20), (ie(8, 100), 30)]
+	/// );
+	/// ```
+	pub fn map_values<W, F>(self, mut f: F) -> DiscreteRangeMap<I, K, W>
+	where
+		F: FnMut(&K, V) -> W,
+	{
+		DiscreteRangeMap::from_sorted_iter_unchecked(self.into_iter().map(
+			|(range, value)| {
+				let value = f(&range, value);
+				(range, value)
+			},
+		))
+	}
+
+	/// Makes a new map with the values given by applying `f` to each
+	/// entry, merging touching ranges whose new values are equal,
+	/// consuming `self`.
+	///
+	/// This means that mapping onto a coarser value type yields the
+	/// minimal set of ranges for the new values.
+	///
+	/// See [`DiscreteRangeMap::map_values()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), 1),
+	/// 	(ie(4, 8), 2),
+	/// 	(ie(8, 10), 3),
+	/// 	(ie(20, 30), 4),
+	/// ])
+	/// .unwrap();
+	///
+	/// let map = map.map_values_coalesce(|_, value| value > 1);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), false), (ie(4, 10), true), (ie(20, 30), true)]
+	/// );
+	/// ```
+	pub fn map_values_coalesce<W, F>(
+		self,
+		mut f: F,
+	) -> DiscreteRangeMap<I, K, W>
+	where
+		F: FnMut(&K, V) -> W,
+		W: Eq,
+	{
+		DiscreteRangeMap::from_sorted_iter_unchecked(coalesce_touching(
+			self.into_iter().map(|(range, value)| {
+				let value = f(&range, value);
+				(range, value)
+			}),
+			W::eq,
+		))
+	}
+
+	/// Merges every run of touching entries with equal values into a
+	/// single entry in one linear pass.
+	///
+	/// Maps built with [`DiscreteRangeMap::insert_strict()`] or
+	/// [`DiscreteRangeMap::insert_overwrite()`] can end up with touching
+	/// entries with equal values, afterwards the map is normalized as
+	/// checked by [`DiscreteRangeMap::is_normalized()`].
+	///
+	/// See [`DiscreteRangeMap::coalesce_by()`] to compare values with a
+	/// custom function instead.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), true),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 10), false),
+	/// 	(ie(12, 14), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.is_normalized(), false);
+	///
+	/// map.coalesce();
+	///
+	/// assert_eq!(map.is_normalized(), true);
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 8), true), (ie(8, 10), false), (ie(12, 14), false)]
+	/// );
+	/// ```
+	pub fn coalesce(&mut self)
+	where
+		V: Eq,
+	{
+		self.coalesce_by(V::eq);
+	}
+
+	/// Merges every run of touching entries whose values are equal
+	/// according to `eq` into a single entry with the value of the
+	/// first entry in the run, in one linear pass.
+	///
+	/// `eq` is given the value of the run so far and the value of the
+	/// next touching entry.
+	///
+	/// If `eq` panics then the runs already merged stay merged and the
+	/// rest of the map is left unchanged.
+	///
+	/// See [`DiscreteRangeMap::coalesce()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), 1),
+	/// 	(ie(4, 8), 3),
+	/// 	(ie(8, 10), 4),
+	/// ])
+	/// .unwrap();
+	///
+	/// // merge values with the same parity
+	/// map.coalesce_by(|a, b| a % 2 == b % 2);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 8), 1), (ie(8, 10), 4)]
+	/// );
+	/// ```
+	pub fn coalesce_by<F>(&mut self, mut eq: F)
+	where
+		F: FnMut(&V, &V) -> bool,
+	{
+		let mut cursor = self.inner.lower_bound_mut(
+			|_: &K| Ordering::Equal,
+			SearchBoundCustom::AllIncluded,
+		);
+
+		loop {
+			// find the whole run before removing any of it so that the
+			// map is left intact if `eq` panics
+			let mut run = cursor.as_cursor();
+			let Some((first, first_value)) = run.key_value() else {
+				break;
+			};
+			let start = first.start();
+			let mut end = first.end();
+			let mut run_len = 1;
+			while let Some((next, next_value)) = run.peek_next() {
+				if end.up() != Some(next.start())
+					|| !eq(first_value, next_value)
+				{
+					break;
+				}
+				end = next.end();
+				run_len += 1;
+				run.move_next();
+			}
+
+			if run_len == 1 {
+				cursor.move_next();
+				continue;
+			}
+
+			let (_, value) = cursor.remove_current().unwrap();
+			for _ in 1..run_len {
+				cursor.remove_current();
+			}
+			// the cursor is now on the entry after the run, or the
+			// "ghost" non-element at the end of the map
+			cursor.insert_before(
+				K::from(InclusiveInterval { start, end }),
+				value,
+			);
+		}
+	}
+
+	/// Returns `true` if no two touching entries in the map have equal
+	/// values, and `false` if any do.
+	///
+	/// See [`DiscreteRangeMap::coalesce()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), true),
+	/// 	(ie(4, 8), false),
+	/// 	(ie(10, 12), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.is_normalized(), true);
+	/// ```
+	pub fn is_normalized(&self) -> bool
+	where
+		V: Eq,
+	{
+		self.iter().tuple_windows().all(
+			|((first, first_value), (second, second_value))| {
+				first.end().up() != Some(second.start())
+					|| first_value != second_value
+			},
+		)
+	}
+
+	/// Moves every range in the map up by `n` points if `n` is positive
+	/// or down by `-n` points if `n` is negative.
+	///
+	/// If any range in the map would overflow past
+	/// [`DiscreteFinite::MIN`] or [`DiscreteFinite::MAX`] then a
+	/// [`ShiftOverflowError`] is returned and the map is not updated.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{DiscreteRangeMap, ShiftOverflowError};
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.shift_all(-1), Ok(()));
+	/// assert_eq!(
+	/// 	map.shift_all(122),
+	/// 	Err(ShiftOverflowError {
+	/// 		start: 0,
+	/// 		end: 6,
+	/// 		n: 122
+	/// 	})
+	/// );
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(0, 3), false), (ie(3, 7), true)]
+	/// );
+	/// ```
+	pub fn shift_all(&mut self, n: i128) -> Result<(), ShiftOverflowError<I>> {
+		// shifting keeps points in order so if the lowest and highest
+		// points in the map can be shifted then so can every other point
+		if let (Some((first, _)), Some((last, _))) =
+			(self.first_entry(), self.last_entry())
+		{
+			InclusiveInterval {
+				start: first.start(),
+				end: last.end(),
+			}
+			.shift(n)?;
+		}
+
+		*self = DiscreteRangeMap::from_sorted_iter_unchecked(
+			core::mem::take(self)
+				.into_iter()
+				.map(|(range, value)| (shift_range_unchecked(range, n), value)),
+		);
+
+		return Ok(());
+	}
+
+	/// Cuts the given range out of the map and inserts the ranges that
+	/// were cut back in moved up by `n` points if `n` is positive or
+	/// down by `-n` points if `n` is negative, overwriting any ranges
+	/// that they land on. The gaps within the given range are not
+	/// moved, so they don't overwrite anything.
+	///
+	/// If the given range would overflow past [`DiscreteFinite::MIN`]
+	/// or [`DiscreteFinite::MAX`] then a [`ShiftOverflowError`] is
+	/// returned and the map is not updated.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 12), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.shift_range(ie(2, 6), 8), Ok(()));
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(ie(1, 2), false),
+	/// 		(ie(6, 8), true),
+	/// 		(ie(8, 10), false),
+	/// 		(ie(10, 12), false),
+	/// 		(ie(12, 14), true),
+	/// 	]
+	/// );
+	/// ```
+	pub fn shift_range<Q>(
+		&mut self,
+		range: Q,
+		n: i128,
+	) -> Result<(), ShiftOverflowError<I>>
+	where
+		Q: RangeType<I>,
+		V: Clone,
+	{
+		invalid_range_panic(range);
+
+		// every range cut out of the map is within the given range so
+		// it can be shifted if the given range can
+		InclusiveInterval {
+			start: range.start(),
+			end: range.end(),
+		}
+		.shift(n)?;
+
+		let cut = self.cut(range).collect::<Vec<_>>();
+		for (range, value) in cut {
+			self.insert_overwrite(shift_range_unchecked(range, n), value);
+		}
+
+		return Ok(());
+	}
+
+	/// Splits the map in two at the given point, returning every entry
+	/// at or after the point and leaving every entry before it.
+	///
+	/// If a range in the map contains the point without starting at it
+	/// then it is cut in two at the point, with its value cloned into
+	/// both halves. See [`DiscreteRangeMap::split_off_with()`] for a
+	/// version that doesn't require `V: Clone`.
+	///
+	/// This splits the underlying tree directly rather than removing
+	/// and re-inserting entries.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// let after = map.split_off(6);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), false), (ie(4, 6), true)]
+	/// );
+	/// assert_eq!(
+	/// 	after.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(6, 8), true), (ie(8, 100), false)]
+	/// );
+	/// ```
+	pub fn split_off(&mut self, point: I) -> DiscreteRangeMap<I, K, V>
+	where
+		V: Clone,
+	{
+		self.split_off_with(point, clone_split)
+	}
+
+	/// Splits the map in two at the given point, using the given
+	/// `split` function rather than `Clone` if a range in the map has
+	/// to be cut in two at the point.
+	///
+	/// `split` is given the range being cut along with its value and
+	/// must return the values for the lower and upper halves in that
+	/// order.
+	///
+	/// See [`DiscreteRangeMap::split_off()`] for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map =
+	/// 	DiscreteRangeMap::from_slice_strict([(ie(0, 10), 10)])
+	/// 		.unwrap();
+	///
+	/// // share the value out in proportion to the length of each half
+	/// let after = map.split_off_with(6, |range, value| {
+	/// 	let lower =
+	/// 		value * (6 - range.start) / (range.end - range.start + 1);
+	/// 	(lower, value - lower)
+	/// });
+	///
+	/// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(ie(0, 6), 6)]);
+	/// assert_eq!(
+	/// 	after.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(6, 10), 4)]
+	/// );
+	/// ```
+	pub fn split_off_with<F>(
+		&mut self,
+		point: I,
+		split: F,
+	) -> DiscreteRangeMap<I, K, V>
+	where
+		F: FnOnce(&K, V) -> (V, V),
+	{
+		let straddling = self
+			.inner
+			.get_key_value(overlapping_comp(point))
+			.map(|(key, _)| *key)
+			.filter(|key| key.start() < point);
+
+		let upper_entry = straddling.map(|straddling| {
+			let value = self.inner.remove(overlapping_comp(point)).unwrap();
+			let (lower_value, upper_value) = split(&straddling, value);
+
+			self.insert_unchecked(
+				K::from(InclusiveInterval {
+					start: straddling.start(),
+					end: point.down().unwrap(),
+				}),
+				lower_value,
+			);
+
+			(
+				K::from(InclusiveInterval {
+					start: point,
+					end: straddling.end(),
+				}),
+				upper_value,
+			)
+		});
+
+		// no range contains the point without starting at it anymore
+		let mut upper = DiscreteRangeMap {
+			inner: self.inner.split_off(overlapping_comp(point)),
+			phantom: PhantomData,
+		};
+
+		if let Some((range, value)) = upper_entry {
+			upper.insert_unchecked(range, value);
+		}
+
+		return upper;
+	}
+
+	/// Moves every entry from `other` into `self`, leaving `other`
+	/// empty.
+	///
+	/// The ranges of the two maps may interleave but must not overlap.
+	/// If any range in `other` overlaps a range in `self` then an
+	/// [`OverlapError`] is returned for the first such range in `other`
+	/// and neither map is updated. As nothing is taken from `other` the
+	/// error's value is `()`.
+	///
+	/// If every range in `other` comes after every range in `self`, or
+	/// vice versa, then the entries of `other` are appended onto the end
+	/// or start of the tree without searching it.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{DiscreteRangeMap, OverlapError};
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// ])
+	/// .unwrap();
+	/// let mut after =
+	/// 	DiscreteRangeMap::from_slice_strict([(ie(8, 100), false)])
+	/// 		.unwrap();
+	/// let mut overlapping =
+	/// 	DiscreteRangeMap::from_slice_strict([(ie(6, 10), true)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(map.append(&mut after), Ok(()));
+	/// assert!(after.is_empty());
+	///
+	/// assert_eq!(
+	/// 	map.append(&mut overlapping),
+	/// 	Err(OverlapError {
+	/// 		range: ie(6, 10),
+	/// 		overlapping: vec![ie(4, 8), ie(8, 100)],
+	/// 		value: (),
+	/// 	})
+	/// );
+	/// assert_eq!(overlapping.len(), 1);
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(8, 100), false)]
+	/// );
+	/// ```
+	pub fn append(
+		&mut self,
+		other: &mut Self,
+	) -> Result<(), OverlapError<K, ()>> {
+		let bounds = |map: &Self| {
+			Option::zip(map.first_entry(), map.last_entry())
+				.map(|((first, _), (last, _))| (first.start(), last.end()))
+		};
+
+		let (Some((self_start, self_end)), Some((other_start, other_end))) =
+			(bounds(self), bounds(other))
+		else {
+			if self.is_empty() {
+				core::mem::swap(self, other);
+			}
+			return Ok(());
+		};
+
+		if other_start > self_end {
+			// inserting before the "ghost" non-element appends to the
+			// end of the map
+			let mut cursor = self.inner.upper_bound_mut(
+				|_: &K| Ordering::Equal,
+				SearchBoundCustom::AllExcluded,
+			);
+			for (range, value) in core::mem::take(other) {
+				cursor.insert_before(range, value);
+			}
+		} else if other_end < self_start {
+			// inserting before the first element prepends to the start
+			// of the map in the same order
+			let mut cursor = self.inner.lower_bound_mut(
+				|_: &K| Ordering::Equal,
+				SearchBoundCustom::AllIncluded,
+			);
+			for (range, value) in core::mem::take(other) {
+				cursor.insert_before(range, value);
+			}
+		} else {
+			if let Some((range, _)) =
+				other.iter().find(|(range, _)| self.overlaps(**range))
+			{
+				return Err(OverlapError {
+					range: *range,
+					overlapping: self
+						.overlapping(*range)
+						.map(|(key, _)| *key)
+						.collect(),
+					value: (),
+				});
+			}
+
+			self.inner.append(&mut other.inner, |(a, _), (b, _)| {
+				a.start().cmp(&b.start())
+			});
+		}
+
+		return Ok(());
+	}
+
+	/// Allocates a `DiscreteRangeMap` and moves the given entries from
+	/// the given slice into the map using
+	/// [`DiscreteRangeMap::insert_strict()`].
+	///
+	/// May return an `Err` while inserting. See
+	/// [`DiscreteRangeMap::insert_strict()`] for details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	/// ```
+	pub fn from_slice_strict<const N: usize>(
+		slice: [(K, V); N],
+	) -> Result<DiscreteRangeMap<I, K, V>, OverlapError<K, V>> {
+		let mut map = DiscreteRangeMap::new();
+		for (range, value) in slice {
+			map.insert_strict(range, value)?;
+		}
+		return Ok(map);
+	}
+
+	/// Collects a `DiscreteRangeMap` from an iterator of (range,
+	/// value) tuples using [`DiscreteRangeMap::insert_strict()`].
+	///
+	/// May return an `Err` while inserting. See
+	/// [`DiscreteRangeMap::insert_strict()`] for details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let slice =
+	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(8, 100), false)];
+	///
+	/// let map: DiscreteRangeMap<_, _, _> =
+	/// 	DiscreteRangeMap::from_iter_strict(
+	/// 		slice.into_iter().filter(|(range, _)| range.start > 2),
+	/// 	)
+	/// 	.unwrap();
+	/// ```
+	pub fn from_iter_strict(
+		iter: impl Iterator<Item = (K, V)>,
+	) -> Result<DiscreteRangeMap<I, K, V>, OverlapError<K, V>> {
+		let mut map = DiscreteRangeMap::new();
+		for (range, value) in iter {
+			map.insert_strict(range, value)?;
+		}
+		return Ok(map);
+	}
+
+	/// Collects a `DiscreteRangeMap` from an iterator of (range,
+	/// value) tuples sorted in ascending order, checking that each
+	/// range comes after the previous one and appending it onto the end
+	/// of the map without searching the tree.
+	///
+	/// This gives the same result as
+	/// [`DiscreteRangeMap::from_iter_strict()`] but is much faster for
+	/// sorted input. If a range doesn't come after the previous one then
+	/// the rest of the iterator is inserted using
+	/// [`DiscreteRangeMap::insert_strict()`], so unsorted input is
+	/// still handled correctly, just without the speedup.
+	///
+	/// May return an `Err` while inserting. See
+	/// [`DiscreteRangeMap::insert_strict()`] for details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{DiscreteRangeMap, OverlapError};
+	///
+	/// let map = DiscreteRangeMap::from_sorted_iter_strict(
+	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(8, 100), false)]
+	/// 		.into_iter(),
+	/// )
+	/// .unwrap();
+	///
+	/// assert_eq!(map.len(), 3);
+	///
+	/// assert_eq!(
+	/// 	DiscreteRangeMap::from_sorted_iter_strict(
+	/// 		[(ie(1, 4), false), (ie(2, 8), true)].into_iter(),
+	/// 	),
+	/// 	Err(OverlapError {
+	/// 		range: ie(2, 8),
+	/// 		overlapping: vec![ie(1, 4)],
+	/// 		value: true,
+	/// 	})
+	/// );
+	/// ```
+	pub fn from_sorted_iter_strict(
+		mut iter: impl Iterator<Item = (K, V)>,
+	) -> Result<DiscreteRangeMap<I, K, V>, OverlapError<K, V>> {
+		let (mut map, unsorted) = Self::from_sorted_prefix(&mut iter, false);
+
+		for (range, value) in unsorted.into_iter().chain(iter) {
+			map.insert_strict(range, value)?;
+		}
+		return Ok(map);
+	}
+
+	/// Collects a `DiscreteRangeMap` from an iterator of (range,
+	/// value) tuples sorted in ascending order, merging ranges which
+	/// touch as if using [`DiscreteRangeMap::insert_merge_touching()`]
+	/// for each one.
+	///
+	/// See [`DiscreteRangeMap::from_sorted_iter_strict()`] for how
+	/// sorted and unsorted input is handled.
+	///
+	/// May return an `Err` while inserting. See
+	/// [`DiscreteRangeMap::insert_merge_touching()`] for details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_sorted_iter_merge_touching(
+	/// 	[(ie(1, 4), false), (ie(4, 8), true), (ie(10, 100), false)]
+	/// 		.into_iter(),
+	/// )
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.into_iter().collect::<Vec<_>>(),
+	/// 	[(ie(1, 8), true), (ie(10, 100), false)]
+	/// );
+	/// ```
+	pub fn from_sorted_iter_merge_touching(
+		mut iter: impl Iterator<Item = (K, V)>,
+	) -> Result<DiscreteRangeMap<I, K, V>, OverlapError<K, V>> {
+		let (mut map, unsorted) = Self::from_sorted_prefix(&mut iter, true);
+
+		for (range, value) in unsorted.into_iter().chain(iter) {
+			map.insert_merge_touching(range, value)?;
+		}
+		return Ok(map);
+	}
+
+	/// Builds a map by appending entries from `iter` onto the end of
+	/// the tree for as long as each range comes after the previous one,
+	/// optionally merging touching ranges.
+	///
+	/// Returns the first entry that didn't come after the previous one,
+	/// if any, which has been taken from `iter` but not inserted.
+	fn from_sorted_prefix(
+		iter: &mut impl Iterator<Item = (K, V)>,
+		merge_touching: bool,
+	) -> (DiscreteRangeMap<I, K, V>, Option<(K, V)>) {
+		let mut map = DiscreteRangeMap::new();
+		let mut unsorted = None;
+
+		// inserting before the "ghost" non-element appends to the end
+		// of the map and leaves the cursor on the "ghost"
+		let mut cursor = map.inner.upper_bound_mut(
+			|_: &K| Ordering::Equal,
+			SearchBoundCustom::AllExcluded,
+		);
+
+		// the last entry is held back so that it can be merged with the
+		// next one if they touch
+		let mut last: Option<(K, V)> = None;
+		for (range, value) in iter.by_ref() {
+			invalid_range_panic(range);
+
+			last = match last {
+				None => Some((range, value)),
+				Some(last) if range.start() <= last.0.end() => {
+					unsorted = Some((range, value));
+					Some(last)
+				}
+				Some((last_range, _))
+					if merge_touching
+						&& last_range.end().up() == Some(range.start()) =>
+				{
+					let merged = K::from(InclusiveInterval {
+						start: last_range.start(),
+						end: range.end(),
+					});
+					Some((merged, value))
+				}
+				Some((last_range, last_value)) => {
+					cursor.insert_before(last_range, last_value);
+					Some((range, value))
+				}
+			};
+
+			if unsorted.is_some() {
+				break;
+			}
+		}
+		if let Some((last_range, last_value)) = last {
+			cursor.insert_before(last_range, last_value);
+		}
+
+		return (map, unsorted);
+	}
+}
+
+// Fallible Variants ==========================
+
+impl<I, K, V> DiscreteRangeMap<I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Fallible version of [`DiscreteRangeMap::overlaps()`].
+	///
+	/// Returns an [`InvalidRangeError`] if the given range is an
+	/// invalid range instead of panicking, otherwise whether any
+	/// entry overlaps it.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::{ie, ii};
+	/// use discrete_range_map::{DiscreteRangeMap, InvalidRangeError};
+	///
+	/// let map =
+	/// 	DiscreteRangeMap::from_slice_strict([(ie(1, 4), false)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(map.try_overlaps(ii(2, 8)), Ok(true));
+	/// assert_eq!(
+	/// 	map.try_overlaps(ii(8, 2)),
+	/// 	Err(InvalidRangeError { start: 8, end: 2 })
+	/// );
+	/// ```
+	pub fn try_overlaps<Q>(
+		&self,
+		range: Q,
+	) -> Result<bool, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.overlaps(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::overlapping()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	pub fn try_overlapping<Q>(
+		&self,
+		range: Q,
+	) -> Result<impl DoubleEndedIterator<Item = (&K, &V)>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.overlapping(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::overlapping_mut()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	pub fn try_overlapping_mut<Q>(
+		&mut self,
+		range: Q,
+	) -> Result<
+		impl DoubleEndedIterator<Item = (&K, &mut V)>,
+		InvalidRangeError<I>,
+	>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.overlapping_mut(range))
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::remove_overlapping()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are removed.
+	pub fn try_remove_overlapping<'a, Q>(
+		&'a mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (K, V)> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.remove_overlapping(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::cut()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are cut.
+	pub fn try_cut<'a, Q>(
+		&'a mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = (K, V)> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+		V: Clone,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.cut(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::cut_with()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned, no entries are cut and
+	/// `split` is never called.
+	pub fn try_cut_with<'a, Q, F>(
+		&'a mut self,
+		range: Q,
+		split: F,
+	) -> Result<impl Iterator<Item = (K, V)> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+		F: FnMut(&K, V) -> (V, V) + 'a,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.cut_with(range, split))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::gaps()`].
+	///
+	/// Returns an [`InvalidRangeError`] if `outer_range` is an
+	/// invalid range rather than panicking.
+	pub fn try_gaps<'a, Q>(
+		&'a self,
+		outer_range: Q,
+	) -> Result<impl Iterator<Item = K> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		check_valid_range(outer_range)?;
+
+		Ok(self.gaps(outer_range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::contains_range()`].
+	///
+	/// Returns an [`InvalidRangeError`] if the given range is an
+	/// invalid range instead of panicking, otherwise whether the
+	/// range is fully covered by the map.
+	pub fn try_contains_range<Q>(
+		&self,
+		range: Q,
+	) -> Result<bool, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.contains_range(range))
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::overlapping_clipped()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	pub fn try_overlapping_clipped<Q>(
+		&self,
+		range: Q,
+	) -> Result<impl DoubleEndedIterator<Item = (K, &V)>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.overlapping_clipped(range))
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::overlapping_clipped_mut()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	pub fn try_overlapping_clipped_mut<Q>(
+		&mut self,
+		range: Q,
+	) -> Result<
+		impl DoubleEndedIterator<Item = (K, &mut V)>,
+		InvalidRangeError<I>,
+	>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.overlapping_clipped_mut(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::segments()`].
+	///
+	/// The given range is checked before the iterator is created, so
+	/// an invalid range gives an [`InvalidRangeError`] rather than a
+	/// panic.
+	pub fn try_segments<Q>(
+		&self,
+		range: Q,
+	) -> Result<
+		impl DoubleEndedIterator<Item = Segment<K, &V>>,
+		InvalidRangeError<I>,
+	>
+	where
+		Q: RangeType<I>,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.segments(range))
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::covered_len_within()`].
+	///
+	/// Returns an [`InvalidRangeError`] if the given range is an
+	/// invalid range instead of panicking. A length too large for a
+	/// `u128` is still reported as `Ok(None)`.
+	pub fn try_covered_len_within<Q>(
+		&self,
+		range: Q,
+	) -> Result<Option<u128>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.covered_len_within(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::gaps_len()`].
+	///
+	/// Returns an [`InvalidRangeError`] if the given range is an
+	/// invalid range instead of panicking. A length too large for a
+	/// `u128` is still reported as `Ok(None)`.
+	pub fn try_gaps_len<Q>(
+		&self,
+		range: Q,
+	) -> Result<Option<u128>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.gaps_len(range))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::insert_strict()`].
+	///
+	/// Both ways the insertion can fail are returned as an
+	/// [`InsertError`]: [`InsertError::InvalidRange`] if the given
+	/// range is an invalid range, where
+	/// [`DiscreteRangeMap::insert_strict()`] would panic, or
+	/// [`InsertError::Overlap`] if it overlaps a range already in the
+	/// map. In either case the map is not updated.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::{ie, ii};
+	/// use discrete_range_map::{
+	/// 	DiscreteRangeMap, InsertError, InvalidRangeError,
+	/// 	OverlapError,
+	/// };
+	///
+	/// let mut map = DiscreteRangeMap::new();
+	///
+	/// assert_eq!(map.try_insert_strict(ie(5, 10), 9), Ok(()));
+	/// assert_eq!(
+	/// 	map.try_insert_strict(ie(5, 10), 2),
+	/// 	Err(InsertError::Overlap(OverlapError {
+	/// 		range: ie(5, 10),
+	/// 		overlapping: vec![ie(5, 10)],
+	/// 		value: 2,
+	/// 	}))
+	/// );
+	/// assert_eq!(
+	/// 	map.try_insert_strict(ii(10, 5), 2),
+	/// 	Err(InsertError::InvalidRange(InvalidRangeError {
+	/// 		start: 10,
+	/// 		end: 5
+	/// 	}))
+	/// );
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn try_insert_strict(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), InsertError<I, K, V>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_strict(range, value)?)
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::insert_merge_touching()`].
+	///
+	/// Returns [`InsertError::InvalidRange`] if the given range is an
+	/// invalid range, or [`InsertError::Overlap`] if it overlaps a
+	/// range already in the map, and in either case the map is not
+	/// updated.
+	pub fn try_insert_merge_touching(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InsertError<I, K, V>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_touching(range, value)?)
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::insert_merge_touching_if_values_equal()`].
+	///
+	/// Returns [`InsertError::InvalidRange`] if the given range is an
+	/// invalid range, or [`InsertError::Overlap`] if it overlaps a
+	/// range already in the map, and in either case the map is not
+	/// updated.
+	pub fn try_insert_merge_touching_if_values_equal(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InsertError<I, K, V>>
+	where
+		V: Eq,
+	{
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_touching_if_values_equal(range, value)?)
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::insert_merge_overlapping()`].
+	///
+	/// Merging can't fail on overlap, so the only error is an
+	/// [`InvalidRangeError`] if the given range is an invalid range,
+	/// in which case the map is not updated.
+	pub fn try_insert_merge_overlapping(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_overlapping(range, value))
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::insert_merge_touching_or_overlapping()`].
+	///
+	/// Merging can't fail on overlap, so the only error is an
+	/// [`InvalidRangeError`] if the given range is an invalid range,
+	/// in which case the map is not updated.
+	pub fn try_insert_merge_touching_or_overlapping(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<K, InvalidRangeError<I>> {
+		check_valid_range(range)?;
+
+		Ok(self.insert_merge_touching_or_overlapping(range, value))
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::insert_overwrite()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned and no entries are
+	/// overwritten.
+	pub fn try_insert_overwrite(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), InvalidRangeError<I>>
+	where
+		V: Clone,
+	{
+		check_valid_range(range)?;
+
+		self.insert_overwrite(range, value);
+		Ok(())
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::insert_overwrite_with()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned, no entries are overwritten
+	/// and `split` is never called.
+	pub fn try_insert_overwrite_with<F>(
+		&mut self,
+		range: K,
+		value: V,
+		split: F,
+	) -> Result<(), InvalidRangeError<I>>
+	where
+		F: FnMut(&K, V) -> (V, V),
+	{
+		check_valid_range(range)?;
+
+		self.insert_overwrite_with(range, value, split);
+		Ok(())
+	}
+
+	/// Fallible version of [`DiscreteRangeMap::insert_combine()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned, the map is not updated and
+	/// `combine` is never called.
+	pub fn try_insert_combine<F>(
+		&mut self,
+		range: K,
+		value: V,
+		combine: F,
+	) -> Result<(), InvalidRangeError<I>>
+	where
+		F: FnMut(&V, &V) -> V,
+		V: Clone,
+	{
+		check_valid_range(range)?;
+
+		self.insert_combine(range, value, combine);
+		Ok(())
+	}
+
+	/// Fallible version of
+	/// [`DiscreteRangeMap::insert_combine_coalesce()`].
+	///
+	/// If the given range is an invalid range then an
+	/// [`InvalidRangeError`] is returned, the map is not updated and
+	/// `combine` is never called.
+	pub fn try_insert_combine_coalesce<F>(
+		&mut self,
+		range: K,
+		value: V,
+		combine: F,
+	) -> Result<(), InvalidRangeError<I>>
+	where
+		F: FnMut(&V, &V) -> V,
+		V: Clone + Eq,
+	{
+		check_valid_range(range)?;
+
+		self.insert_combine_coalesce(range, value, combine);
+		Ok(())
+	}
+}
+
+impl<I, K, V> DiscreteRangeMap<I, K, V> {
+	/// Makes a new, empty `DiscreteRangeMap`.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::{DiscreteRangeMap, InclusiveInterval};
 	///
 	/// let map: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool> =
 	/// 	DiscreteRangeMap::new();
@@ -1302,125 +3506,593 @@ impl<I, K, V> DiscreteRangeMap<I, K, V> {
 		self.inner.len()
 	}
 
-	/// Returns `true` if the map contains no ranges, and
-	/// `false` if it does.
-	///
-	/// # Examples
-	/// ```
-	/// use discrete_range_map::test_ranges::ie;
-	/// use discrete_range_map::DiscreteRangeMap;
-	///
-	/// let mut map = DiscreteRangeMap::new();
-	///
-	/// assert_eq!(map.is_empty(), true);
-	/// map.insert_strict(ie(0, 1), false).unwrap();
-	/// assert_eq!(map.is_empty(), false);
-	/// ```
-	pub fn is_empty(&self) -> bool {
-		self.inner.is_empty()
+	/// Returns `true` if the map contains no ranges, and
+	/// `false` if it does.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::new();
+	///
+	/// assert_eq!(map.is_empty(), true);
+	/// map.insert_strict(ie(0, 1), false).unwrap();
+	/// assert_eq!(map.is_empty(), false);
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	/// Returns an iterator over every entry in the map in ascending
+	/// order.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// let mut iter = map.iter();
+	///
+	/// assert_eq!(iter.next(), Some((&ie(1, 4), &false)));
+	/// assert_eq!(iter.next(), Some((&ie(4, 8), &true)));
+	/// assert_eq!(iter.next(), Some((&ie(8, 100), &false)));
+	/// assert_eq!(iter.next(), None);
+	/// ```
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+		self.inner.iter()
+	}
+
+	/// Returns an mutable iterator over every entry in the map in
+	/// ascending order.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let mut map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// for (range, value) in map.iter_mut() {
+	/// 	if *range == ie(4, 8) {
+	/// 		*value = false
+	/// 	} else {
+	/// 		*value = true
+	/// 	}
+	/// }
+	/// ```
+	pub fn iter_mut(
+		&mut self,
+	) -> impl DoubleEndedIterator<Item = (&K, &mut V)> {
+		self.inner.iter_mut()
+	}
+
+	/// Returns the first entry in the map, if any.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::DiscreteRangeMap;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(map.first_entry(), Some((&ie(1, 4), &false)));
+	/// ```
+	pub fn first_entry(&self) -> Option<(&K, &V)> {
+		self.inner.first_key_value()
+	}
+
+	/// Returns the last entry in the map, if any.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::DiscreteRangeMap;
+	/// use discrete_range_map::test_ranges::ie;
+	///
+	/// let map = DiscreteRangeMap::from_slice_strict([
+	/// 	(ie(1, 4), false),
+	/// 	(ie(4, 8), true),
+	/// 	(ie(8, 100), false),
+	/// ])
+	/// .unwrap();
+	///
+	/// assert_eq!(
+	/// 	map.last_entry(),
+	/// 	Some((&ie(8, 100), &false))
+	/// );
+	pub fn last_entry(&self) -> Option<(&K, &V)> {
+		self.inner.last_key_value()
+	}
+}
+
+// Entry API ==========================
+
+/// A view into a single point in a [`DiscreteRangeMap`], which may
+/// either be covered by a range in the map or be in a gap.
+///
+/// This `enum` is constructed from the
+/// [`DiscreteRangeMap::entry_at_point()`] method.
+pub enum Entry<'a, I, K, V> {
+	/// The point is covered by a range in the map.
+	Occupied(OccupiedEntry<'a, I, K, V>),
+	/// The point is not covered by any range in the map.
+	Vacant(VacantEntry<'a, I, K, V>),
+}
+
+/// A view into an entry of a [`DiscreteRangeMap`] which covers the
+/// point it was looked up with. It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, I, K, V> {
+	// the cursor from the search in `entry_at_point()`, pointing at
+	// the entry
+	cursor: BTreeMapCursorMut<'a, K, V>,
+	phantom: PhantomData<I>,
+}
+
+/// A view into the maximally-sized gap of a [`DiscreteRangeMap`] at
+/// the point it was looked up with. It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, I, K, V> {
+	// the cursor from the search in `entry_at_point()`, pointing at
+	// the entry before the gap or the "ghost" non-element if there
+	// isn't one
+	cursor: BTreeMapCursorMut<'a, K, V>,
+	point: I,
+	gap: K,
+}
+
+impl<'a, I, K, V> OccupiedEntry<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Returns a reference to the range of the entry.
+	pub fn range(&self) -> &K {
+		self.cursor.key().unwrap()
+	}
+	/// Returns a reference to the value of the entry.
+	pub fn get(&self) -> &V {
+		self.cursor.value().unwrap()
+	}
+	/// Returns a mutable reference to the value of the entry.
+	pub fn get_mut(&mut self) -> &mut V {
+		self.cursor.value_mut().unwrap()
+	}
+	/// Sets the value of the entry, returning the entry's old value.
+	pub fn insert(&mut self, value: V) -> V {
+		core::mem::replace(self.get_mut(), value)
+	}
+	/// Removes the entry from the map, returning its value.
+	pub fn remove(self) -> V {
+		self.remove_entry().1
+	}
+	/// Removes the entry from the map, returning its range and value.
+	pub fn remove_entry(mut self) -> (K, V) {
+		self.cursor.remove_current().unwrap()
+	}
+}
+
+impl<'a, I, K, V> VacantEntry<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Returns the point that the entry was looked up with.
+	pub fn point(&self) -> I {
+		self.point
+	}
+	/// Returns a reference to the maximally-sized gap at the point that
+	/// the entry was looked up with.
+	pub fn gap(&self) -> &K {
+		&self.gap
+	}
+
+	/// Adds a new entry to the map in all or part of the gap without
+	/// modifying other entries.
+	///
+	/// If the given range extends outside of the gap then an
+	/// [`OverlapError`] is returned and the map is not updated.
+	///
+	/// See [`DiscreteRangeMap::insert_strict()`] for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_strict(
+		mut self,
+		range: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>> {
+		let value = self.check_in_gap(range, value)?;
+
+		self.cursor.insert_after(range, value);
+
+		return Ok(());
+	}
+	/// Adds a new entry to the map in all or part of the gap and merges
+	/// into other ranges in the map which touch it.
+	///
+	/// If the given range extends outside of the gap then an
+	/// [`OverlapError`] is returned and the map is not updated.
+	///
+	/// See [`DiscreteRangeMap::insert_merge_touching()`] for more
+	/// details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_merge_touching(
+		self,
+		range: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>> {
+		self.insert_merge_touching_if(range, value, |_, _| true)
+	}
+	/// Adds a new entry to the map in all or part of the gap and merges
+	/// into other ranges in the map which touch it if the touching
+	/// ranges' values are equal to the value being inserted.
+	///
+	/// If the given range extends outside of the gap then an
+	/// [`OverlapError`] is returned and the map is not updated.
+	///
+	/// See [`DiscreteRangeMap::insert_merge_touching_if_values_equal()`]
+	/// for more details.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	pub fn insert_merge_touching_if_values_equal(
+		self,
+		range: K,
+		value: V,
+	) -> Result<K, OverlapError<K, V>>
+	where
+		V: Eq,
+	{
+		self.insert_merge_touching_if(range, value, |touching, value| {
+			touching == value
+		})
+	}
+
+	fn insert_merge_touching_if(
+		mut self,
+		range: K,
+		value: V,
+		mut can_merge: impl FnMut(&V, &V) -> bool,
+	) -> Result<K, OverlapError<K, V>> {
+		let value = self.check_in_gap(range, value)?;
+
+		// the gap is maximally-sized so only ranges sharing an end with
+		// the gap can touch the entries either side of it
+		let merge_before = range.start() == self.gap.start()
+			&& self
+				.cursor
+				.value()
+				.is_some_and(|before| can_merge(before, &value));
+		let merge_after = range.end() == self.gap.end()
+			&& self
+				.cursor
+				.peek_next()
+				.is_some_and(|(_, after)| can_merge(after, &value));
+
+		let mut merged = InclusiveInterval {
+			start: range.start(),
+			end: range.end(),
+		};
+		if merge_after {
+			self.cursor.move_next();
+			let (after, _) =
+				self.cursor.remove_current_and_move_back().unwrap();
+			merged.end = after.end();
+		}
+		if merge_before {
+			let (before, _) =
+				self.cursor.remove_current_and_move_back().unwrap();
+			merged.start = before.start();
+		}
+
+		let merged = K::from(merged);
+		self.cursor.insert_after(merged, value);
+
+		return Ok(merged);
+	}
+	fn check_in_gap(
+		&self,
+		range: K,
+		value: V,
+	) -> Result<V, OverlapError<K, V>> {
+		invalid_range_panic(range);
+
+		if range.start() < self.gap.start() || range.end() > self.gap.end() {
+			return Err(self.overlap_error(range, value));
+		}
+
+		return Ok(value);
+	}
+	/// Finds the ranges overlapping `range` by walking outwards from
+	/// the gap rather than searching the tree again.
+	fn overlap_error(&self, range: K, value: V) -> OverlapError<K, V> {
+		let mut before = self.cursor.as_cursor();
+		let mut after = before.clone();
+		after.move_next();
+
+		let mut overlapping = Vec::new();
+		while let Some(key) = before.key() {
+			if key.end() < range.start() {
+				break;
+			}
+			if overlaps(*key, range) {
+				overlapping.push(*key);
+			}
+			before.move_prev();
+		}
+		overlapping.reverse();
+		while let Some(key) = after.key() {
+			if key.start() > range.end() {
+				break;
+			}
+			if overlaps(*key, range) {
+				overlapping.push(*key);
+			}
+			after.move_next();
+		}
+
+		return OverlapError {
+			range,
+			overlapping,
+			value,
+		};
+	}
+}
+
+// Cursor API ==========================
+
+/// A segment of a [`DiscreteRangeMap`] stepped over by a [`Cursor`] or
+/// a [`CursorMut`].
+///
+/// `V` is `&V` for a [`Cursor`] and `&mut V` for a [`CursorMut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorItem<'a, K, V> {
+	/// An entry in the map.
+	Entry(&'a K, V),
+	/// A maximally-sized gap between the entries in the map.
+	Gap(K),
+}
+
+/// A cursor over the entries and gaps of a [`DiscreteRangeMap`].
+///
+/// The entries of a map along with the maximally-sized gaps between
+/// them cover every point from `I::MIN` to `I::MAX`. A `Cursor` sits
+/// between two of these segments and can step over them in either
+/// direction by following the underlying tree rather than searching
+/// it.
+///
+/// This `struct` is constructed from the
+/// [`DiscreteRangeMap::cursor_at_point()`] method.
+pub struct Cursor<'a, I, K, V> {
+	// points at the entry before the cursor, the "ghost" non-element
+	// meaning the start of the map
+	inner: BTreeMapCursor<'a, K, V>,
+	// whether the cursor is after the gap following `inner`'s entry
+	// rather than directly after the entry itself
+	after_gap: bool,
+	phantom: PhantomData<I>,
+}
+
+/// A cursor over the entries and gaps of a [`DiscreteRangeMap`] with
+/// editing operations.
+///
+/// See [`Cursor`] for more details.
+///
+/// This `struct` is constructed from the
+/// [`DiscreteRangeMap::cursor_mut_at_point()`] method.
+pub struct CursorMut<'a, I, K, V> {
+	// see `Cursor`
+	inner: BTreeMapCursorMut<'a, K, V>,
+	after_gap: bool,
+	phantom: PhantomData<I>,
+}
+
+impl<'a, I, K, V> Cursor<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Steps the cursor backward over the previous segment and returns
+	/// it, or returns `None` if the cursor is at the start of the map.
+	pub fn prev(&mut self) -> Option<CursorItem<'a, K, &'a V>> {
+		if self.after_gap {
+			self.after_gap = false;
+			return gap_after(&self.inner).map(CursorItem::Gap);
+		}
+
+		let (range, value) = self.inner.key_value()?;
+		self.inner.move_prev();
+		self.after_gap = gap_after(&self.inner).is_some();
+
+		return Some(CursorItem::Entry(range, value));
+	}
+	/// Returns the next segment without moving the cursor.
+	pub fn peek_next(&self) -> Option<CursorItem<'a, K, &'a V>> {
+		self.clone().next()
+	}
+	/// Returns the previous segment without moving the cursor.
+	pub fn peek_prev(&self) -> Option<CursorItem<'a, K, &'a V>> {
+		self.clone().prev()
+	}
+}
+
+impl<'a, I, K, V> CursorMut<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	/// Steps the cursor forward over the next segment and returns it,
+	/// or returns `None` if the cursor is at the end of the map.
+	// this can't be an `Iterator` as the items borrow from the cursor
+	#[allow(clippy::should_implement_trait)]
+	pub fn next(&mut self) -> Option<CursorItem<'_, K, &mut V>> {
+		if !self.after_gap {
+			if let Some(gap) = gap_after(&self.inner.as_cursor()) {
+				self.after_gap = true;
+				return Some(CursorItem::Gap(gap));
+			}
+		}
+
+		self.inner.as_cursor().peek_next()?;
+		self.inner.move_next();
+		self.after_gap = false;
+
+		let (range, value) = self.inner.key_value_mut().unwrap();
+		return Some(CursorItem::Entry(range, value));
+	}
+	/// Steps the cursor backward over the previous segment and returns
+	/// it, or returns `None` if the cursor is at the start of the map.
+	pub fn prev(&mut self) -> Option<CursorItem<'_, K, &mut V>> {
+		if self.after_gap {
+			self.after_gap = false;
+			return gap_after(&self.inner.as_cursor()).map(CursorItem::Gap);
+		}
+
+		self.inner.key()?;
+		self.inner.move_prev();
+		self.after_gap = gap_after(&self.inner.as_cursor()).is_some();
+
+		let (range, value) = self.inner.peek_next().unwrap();
+		return Some(CursorItem::Entry(range, value));
 	}
+	/// Returns the next segment without moving the cursor.
+	pub fn peek_next(&mut self) -> Option<CursorItem<'_, K, &mut V>> {
+		if !self.after_gap {
+			if let Some(gap) = gap_after(&self.inner.as_cursor()) {
+				return Some(CursorItem::Gap(gap));
+			}
+		}
 
-	/// Returns an iterator over every entry in the map in ascending
-	/// order.
-	///
-	/// # Examples
-	/// ```
-	/// use discrete_range_map::test_ranges::ie;
-	/// use discrete_range_map::DiscreteRangeMap;
-	///
-	/// let map = DiscreteRangeMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
-	/// ])
-	/// .unwrap();
-	///
-	/// let mut iter = map.iter();
-	///
-	/// assert_eq!(iter.next(), Some((&ie(1, 4), &false)));
-	/// assert_eq!(iter.next(), Some((&ie(4, 8), &true)));
-	/// assert_eq!(iter.next(), Some((&ie(8, 100), &false)));
-	/// assert_eq!(iter.next(), None);
-	/// ```
-	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
-		self.inner.iter()
+		let (range, value) = self.inner.peek_next()?;
+		return Some(CursorItem::Entry(range, value));
 	}
+	/// Returns the previous segment without moving the cursor.
+	pub fn peek_prev(&mut self) -> Option<CursorItem<'_, K, &mut V>> {
+		if self.after_gap {
+			return gap_after(&self.inner.as_cursor()).map(CursorItem::Gap);
+		}
 
-	/// Returns an mutable iterator over every entry in the map in
-	/// ascending order.
-	///
-	/// # Examples
-	/// ```
-	/// use discrete_range_map::test_ranges::ie;
-	/// use discrete_range_map::DiscreteRangeMap;
-	///
-	/// let mut map = DiscreteRangeMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
-	/// ])
-	/// .unwrap();
-	///
-	/// for (range, value) in map.iter_mut() {
-	/// 	if *range == ie(4, 8) {
-	/// 		*value = false
-	/// 	} else {
-	/// 		*value = true
-	/// 	}
-	/// }
-	/// ```
-	pub fn iter_mut(
-		&mut self,
-	) -> impl DoubleEndedIterator<Item = (&K, &mut V)> {
-		self.inner.iter_mut()
+		let (range, value) = self.inner.key_value_mut()?;
+		return Some(CursorItem::Entry(range, value));
+	}
+	/// Returns a read-only cursor at the same position as this one.
+	pub fn as_cursor(&self) -> Cursor<'_, I, K, V> {
+		Cursor {
+			inner: self.inner.as_cursor(),
+			after_gap: self.after_gap,
+			phantom: PhantomData,
+		}
 	}
 
-	/// Returns the first entry in the map, if any.
+	/// Adds a new entry to the map in the gap after the cursor without
+	/// modifying other entries, leaving the cursor just after the new
+	/// entry.
 	///
-	/// # Examples
-	/// ```
-	/// use discrete_range_map::test_ranges::ie;
-	/// use discrete_range_map::DiscreteRangeMap;
+	/// If the given range extends outside of the gap then an
+	/// [`OverlapError`] is returned and the map is not updated.
 	///
-	/// let map = DiscreteRangeMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
-	/// ])
-	/// .unwrap();
+	/// # Panics
 	///
-	/// assert_eq!(map.first_entry(), Some((&ie(1, 4), &false)));
-	/// ```
-	pub fn first_entry(&self) -> Option<(&K, &V)> {
-		self.inner.first_key_value()
+	/// Panics if the given range is an invalid range, or if the next
+	/// segment is not a gap that the given range overlaps.
+	pub fn insert_strict(
+		&mut self,
+		range: K,
+		value: V,
+	) -> Result<(), OverlapError<K, V>> {
+		invalid_range_panic(range);
+
+		let gap = match self.as_cursor().peek_next() {
+			Some(CursorItem::Gap(gap)) if overlaps(gap, range) => gap,
+			_ => panic!(
+				"range given to a CursorMut must overlap the gap after the cursor"
+			),
+		};
+
+		// the gap is maximally-sized so any range overlapping it that
+		// extends past it must overlap the entries either side
+		if range.start() < gap.start() || range.end() > gap.end() {
+			let cursor = self.inner.as_cursor();
+
+			let mut before = cursor.clone();
+			let mut before = core::iter::from_fn(|| {
+				let key = before.key().filter(|key| overlaps(**key, range));
+				before.move_prev();
+				key.copied()
+			})
+			.collect::<Vec<_>>();
+			before.reverse();
+
+			let mut after = cursor;
+			after.move_next();
+			let after = core::iter::from_fn(|| {
+				let key = after.key().filter(|key| overlaps(**key, range));
+				after.move_next();
+				key.copied()
+			});
+
+			return Err(OverlapError {
+				range,
+				overlapping: before.into_iter().chain(after).collect(),
+				value,
+			});
+		}
+
+		self.inner.insert_after(range, value);
+		self.inner.move_next();
+		self.after_gap = false;
+
+		return Ok(());
 	}
+	/// Removes the next segment from the map and returns it, if it is
+	/// an entry, leaving the cursor just before the gap the entry's
+	/// range becomes part of.
+	///
+	/// If the next segment is a gap, or the cursor is at the end of
+	/// the map, then `None` is returned and the map is not updated.
+	pub fn remove_next(&mut self) -> Option<(K, V)> {
+		if let Some(CursorItem::Gap(_)) | None = self.as_cursor().peek_next() {
+			return None;
+		}
 
-	/// Returns the last entry in the map, if any.
-	///
-	/// # Examples
-	/// ```
-	/// use discrete_range_map::DiscreteRangeMap;
-	/// use discrete_range_map::test_ranges::ie;
-	///
-	/// let map = DiscreteRangeMap::from_slice_strict([
-	/// 	(ie(1, 4), false),
-	/// 	(ie(4, 8), true),
-	/// 	(ie(8, 100), false),
-	/// ])
-	/// .unwrap();
-	///
-	/// assert_eq!(
-	/// 	map.last_entry(),
-	/// 	Some((&ie(8, 100), &false))
-	/// );
-	pub fn last_entry(&self) -> Option<(&K, &V)> {
-		self.inner.last_key_value()
+		self.inner.move_next();
+		let entry = self.inner.remove_current_and_move_back();
+		self.after_gap = false;
+
+		return entry;
 	}
 }
 
 // Helper Functions ==========================
 
-fn invalid_range_panic<Q, I>(range: Q)
+pub(crate) fn invalid_range_panic<Q, I>(range: Q)
 where
 	I: PointType,
 	Q: RangeType<I>,
@@ -1432,6 +4104,160 @@ where
 	}
 }
 
+pub(crate) fn check_valid_range<Q, I>(
+	range: Q,
+) -> Result<(), InvalidRangeError<I>>
+where
+	I: PointType,
+	Q: RangeType<I>,
+{
+	if !is_valid_range(range) {
+		return Err(InvalidRangeError {
+			start: range.start(),
+			end: range.end(),
+		});
+	}
+
+	return Ok(());
+}
+/// Returns the error for the last of the deserialized `keys`
+/// overlapping `range`, listing the indices of the earlier keys it
+/// overlapped.
+pub(crate) fn overlap_deserialize_error<E, I, K>(keys: &[K], range: K) -> E
+where
+	E: serde::de::Error,
+	I: PointType,
+	K: RangeType<I>,
+{
+	let (_, earlier) = keys.split_last().unwrap();
+	let overlapped = earlier
+		.iter()
+		.positions(|key| overlaps(*key, range))
+		.collect::<Vec<_>>();
+
+	return E::custom(format_args!(
+		"element {} overlaps earlier element(s) {:?}",
+		earlier.len(),
+		overlapped
+	));
+}
+
+/// Merges every run of touching entries in an ascending iterator whose
+/// values are equal according to `eq`, keeping the first value.
+fn coalesce_touching<I, K, V, F>(
+	iter: impl Iterator<Item = (K, V)>,
+	mut eq: F,
+) -> impl Iterator<Item = (K, V)>
+where
+	I: PointType,
+	K: RangeType<I>,
+	F: FnMut(&V, &V) -> bool,
+{
+	iter.coalesce(move |(first, first_value), (second, second_value)| {
+		if first.end().up() == Some(second.start())
+			&& eq(&first_value, &second_value)
+		{
+			Ok((
+				K::from(InclusiveInterval {
+					start: first.start(),
+					end: second.end(),
+				}),
+				first_value,
+			))
+		} else {
+			Err(((first, first_value), (second, second_value)))
+		}
+	})
+}
+/// Returns the total number of points in the given ranges, or `None`
+/// if there are `2^128` of them.
+fn sum_lens<I, K>(ranges: impl Iterator<Item = K>) -> Option<u128>
+where
+	I: PointType + DiscreteMeasure,
+	K: RangeType<I>,
+{
+	ranges
+		.map(|range| range.len())
+		.try_fold(0_u128, |total, len| total.checked_add(len?))
+}
+/// Shifts a range which is known not to overflow.
+fn shift_range_unchecked<I, K>(range: K, n: i128) -> K
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	let interval = InclusiveInterval {
+		start: range.start(),
+		end: range.end(),
+	};
+
+	K::from(
+		interval
+			.shift(n)
+			.ok()
+			.expect("the shift is known not to overflow"),
+	)
+}
+/// Trims a range overlapping `range` to the part inside `range`.
+pub(crate) fn clip_range<I, K, Q>(key: K, range: Q) -> K
+where
+	I: PointType,
+	K: RangeType<I>,
+	Q: RangeType<I>,
+{
+	K::from(cut_range(key, range).inside_cut.unwrap())
+}
+/// Returns the gap between the entry a [`Cursor`]'s inner cursor is
+/// pointing to and the entry after it, if the gap isn't empty.
+fn gap_after<I, K, V>(cursor: &BTreeMapCursor<K, V>) -> Option<K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	let start = match cursor.key() {
+		Some(before) => before.end().up()?,
+		None => I::MIN,
+	};
+	let end = match cursor.peek_next() {
+		Some((after, _)) => after.start().down()?,
+		None => I::MAX,
+	};
+
+	return (start <= end).then(|| K::from(InclusiveInterval { start, end }));
+}
+/// Whether a cursor positioned at `point` should be after the gap
+/// following the inner cursor's entry, which is only the case if that
+/// gap exists and `point` is in the entry after it instead.
+fn starts_after_gap<I, K, V>(cursor: &BTreeMapCursor<K, V>, point: I) -> bool
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	gap_after(cursor).is_some_and(|gap| !gap.contains(point))
+}
+/// Returns the value of a segment after combining `value` into it
+/// with `combine`, where `None` is a gap.
+fn combined<V, F>(
+	old_value: Option<&V>,
+	value: &V,
+	combine: &mut F,
+) -> Option<V>
+where
+	F: FnMut(&V, &V) -> V,
+	V: Clone,
+{
+	Some(match old_value {
+		Some(old_value) => combine(old_value, value),
+		None => value.clone(),
+	})
+}
+fn clone_split<K, V>(_: &K, value: V) -> (V, V)
+where
+	V: Clone,
+{
+	(value.clone(), value)
+}
+
 fn double_comp<K, I>() -> impl FnMut(&K, &K) -> Ordering
 where
 	I: PointType,
@@ -1468,6 +4294,9 @@ where
 }
 
 /// A range that has **Inclusive** end-points.
+// valid ranges always contain at least one point so an `is_empty()`
+// would always return `false`
+#[allow(clippy::len_without_is_empty)]
 pub trait InclusiveRange<I> {
 	fn start(&self) -> I;
 	fn end(&self) -> I;
@@ -1486,6 +4315,16 @@ pub trait InclusiveRange<I> {
 		self.start() <= self.end()
 	}
 
+	/// Returns the number of points in the range, or `None` if there
+	/// are `2^128` of them which is only possible for a range covering
+	/// every point of a 128-bit type.
+	fn len(&self) -> Option<u128>
+	where
+		I: PointType + DiscreteMeasure,
+	{
+		I::distance(self.start(), self.end()).checked_add(1)
+	}
+
 	///requires that self comes before other and they don't overlap
 	fn touches_ordered(&self, other: &Self) -> bool
 	where
@@ -1516,6 +4355,41 @@ pub trait InclusiveRange<I> {
 
 // Trait Impls ==========================
 
+impl<'a, I, K, V> Iterator for Cursor<'a, I, K, V>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Item = CursorItem<'a, K, &'a V>;
+
+	/// Steps the cursor forward over the next segment and returns it,
+	/// or returns `None` if the cursor is at the end of the map.
+	fn next(&mut self) -> Option<Self::Item> {
+		if !self.after_gap {
+			if let Some(gap) = gap_after(&self.inner) {
+				self.after_gap = true;
+				return Some(CursorItem::Gap(gap));
+			}
+		}
+
+		let (range, value) = self.inner.peek_next()?;
+		self.inner.move_next();
+		self.after_gap = false;
+
+		return Some(CursorItem::Entry(range, value));
+	}
+}
+
+impl<'a, I, K, V> Clone for Cursor<'a, I, K, V> {
+	fn clone(&self) -> Self {
+		Cursor {
+			inner: self.inner.clone(),
+			after_gap: self.after_gap,
+			phantom: PhantomData,
+		}
+	}
+}
+
 impl<I, K, V> IntoIterator for DiscreteRangeMap<I, K, V> {
 	type Item = (K, V);
 	type IntoIter = IntoIter<I, K, V>;
@@ -1526,6 +4400,144 @@ impl<I, K, V> IntoIterator for DiscreteRangeMap<I, K, V> {
 		};
 	}
 }
+/// The draining iterator returned from
+/// [`DiscreteRangeMap::remove_overlapping()`].
+struct RemoveOverlapping<'a, I, Q, K, V>
+where
+	I: PointType,
+	Q: RangeType<I>,
+	K: RangeType<I>,
+{
+	cursor: BTreeMapCursorMut<'a, K, V>,
+	range: Q,
+	phantom: PhantomData<I>,
+}
+impl<'a, I, Q, K, V> Iterator for RemoveOverlapping<'a, I, Q, K, V>
+where
+	I: PointType,
+	Q: RangeType<I>,
+	K: RangeType<I>,
+{
+	type Item = (K, V);
+	fn next(&mut self) -> Option<Self::Item> {
+		if self
+			.cursor
+			.key()
+			.is_some_and(|inner_range| overlaps(*inner_range, self.range))
+		{
+			self.cursor.remove_current()
+		} else {
+			None
+		}
+	}
+}
+impl<'a, I, Q, K, V> Drop for RemoveOverlapping<'a, I, Q, K, V>
+where
+	I: PointType,
+	Q: RangeType<I>,
+	K: RangeType<I>,
+{
+	fn drop(&mut self) {
+		self.for_each(drop);
+	}
+}
+
+/// The iterator returned from [`DiscreteRangeMap::segments()`].
+struct Segments<'a, I, K, V, O> {
+	overlapping: O,
+	// entries taken from `overlapping` but not yet yielded as they
+	// come after a gap
+	front: Option<(&'a K, &'a V)>,
+	back: Option<(&'a K, &'a V)>,
+	// the part of the range not yet yielded
+	remaining: Option<InclusiveInterval<I>>,
+}
+impl<'a, I, K, V, O> Iterator for Segments<'a, I, K, V, O>
+where
+	I: PointType,
+	K: RangeType<I>,
+	O: DoubleEndedIterator<Item = (&'a K, &'a V)>,
+{
+	type Item = Segment<K, &'a V>;
+	fn next(&mut self) -> Option<Self::Item> {
+		let remaining = self.remaining?;
+
+		let entry = self
+			.front
+			.take()
+			.or_else(|| self.overlapping.next())
+			.or_else(|| self.back.take());
+
+		let segment = match entry {
+			Some((range, _)) if range.start() > remaining.start => {
+				self.front = entry;
+				Segment::Gap(K::from(InclusiveInterval {
+					start: remaining.start,
+					end: range.start().down().unwrap(),
+				}))
+			}
+			Some((range, value)) => {
+				Segment::Filled(clip_range(*range, remaining), value)
+			}
+			None => Segment::Gap(K::from(remaining)),
+		};
+
+		let (Segment::Filled(range, _) | Segment::Gap(range)) = segment;
+		self.remaining = range
+			.end()
+			.up()
+			.filter(|start| *start <= remaining.end)
+			.map(|start| InclusiveInterval {
+				start,
+				end: remaining.end,
+			});
+
+		return Some(segment);
+	}
+}
+impl<'a, I, K, V, O> DoubleEndedIterator for Segments<'a, I, K, V, O>
+where
+	I: PointType,
+	K: RangeType<I>,
+	O: DoubleEndedIterator<Item = (&'a K, &'a V)>,
+{
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let remaining = self.remaining?;
+
+		let entry = self
+			.back
+			.take()
+			.or_else(|| self.overlapping.next_back())
+			.or_else(|| self.front.take());
+
+		let segment = match entry {
+			Some((range, _)) if range.end() < remaining.end => {
+				self.back = entry;
+				Segment::Gap(K::from(InclusiveInterval {
+					start: range.end().up().unwrap(),
+					end: remaining.end,
+				}))
+			}
+			Some((range, value)) => {
+				Segment::Filled(clip_range(*range, remaining), value)
+			}
+			None => Segment::Gap(K::from(remaining)),
+		};
+
+		let (Segment::Filled(range, _) | Segment::Gap(range)) = segment;
+		self.remaining = range
+			.start()
+			.down()
+			.filter(|end| *end >= remaining.start)
+			.map(|end| InclusiveInterval {
+				start: remaining.start,
+				end,
+			});
+
+		return Some(segment);
+	}
+}
+
 /// An owning iterator over the entries of a [`DiscreteRangeMap`].
 ///
 /// This `struct` is created by the [`into_iter`] method on
@@ -1545,6 +4557,95 @@ impl<I, K, V> Iterator for IntoIter<I, K, V> {
 	}
 }
 
+impl<K, V> fmt::Display for OverlapError<K, V>
+where
+	K: Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"the range {:?} overlaps the existing ranges {:?}",
+			self.range, self.overlapping
+		)
+	}
+}
+
+impl<K, V> Error for OverlapError<K, V>
+where
+	K: Debug,
+	V: Debug,
+{
+}
+
+impl<I> fmt::Display for InvalidRangeError<I>
+where
+	I: Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"invalid range with start {:?} and end {:?}, see https://docs.rs/discrete_range_map/latest/discrete_range_map/#invalid-ranges",
+			self.start, self.end
+		)
+	}
+}
+
+impl<I> Error for InvalidRangeError<I> where I: Debug {}
+
+impl<I> fmt::Display for ShiftOverflowError<I>
+where
+	I: Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"shifting the range with start {:?} and end {:?} by {} would overflow",
+			self.start, self.end, self.n
+		)
+	}
+}
+
+impl<I> Error for ShiftOverflowError<I> where I: Debug {}
+
+impl<I, K, V> fmt::Display for InsertError<I, K, V>
+where
+	I: Debug,
+	K: Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			InsertError::InvalidRange(error) => write!(f, "{error}"),
+			InsertError::Overlap(error) => write!(f, "{error}"),
+		}
+	}
+}
+
+impl<I, K, V> Error for InsertError<I, K, V>
+where
+	I: Debug + 'static,
+	K: Debug + 'static,
+	V: Debug + 'static,
+{
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			InsertError::InvalidRange(error) => Some(error),
+			InsertError::Overlap(error) => Some(error),
+		}
+	}
+}
+
+impl<I, K, V> From<InvalidRangeError<I>> for InsertError<I, K, V> {
+	fn from(error: InvalidRangeError<I>) -> Self {
+		InsertError::InvalidRange(error)
+	}
+}
+
+impl<I, K, V> From<OverlapError<K, V>> for InsertError<I, K, V> {
+	fn from(error: OverlapError<K, V>) -> Self {
+		InsertError::Overlap(error)
+	}
+}
+
 impl<I, K, V> Default for DiscreteRangeMap<I, K, V> {
 	fn default() -> Self {
 		DiscreteRangeMap {
@@ -1611,12 +4712,29 @@ where
 	where
 		A: SeqAccess<'de>,
 	{
-		let mut map = DiscreteRangeMap::new();
-		while let Some((range_bounds, value)) = access.next_element()? {
-			map.insert_strict(range_bounds, value)
-				.map_err(|_| serde::de::Error::custom("ranges overlap"))?;
+		let mut access_error = None;
+		// `K` isn't required to be `Debug` so the keys are kept to
+		// report an overlap by the indices of the elements instead
+		let mut keys = Vec::new();
+		// fused so that `access` isn't polled again after an error
+		let elements = core::iter::from_fn(|| {
+			access
+				.next_element()
+				.map_err(|error| access_error = Some(error))
+				.ok()
+				.flatten()
+		})
+		.fuse()
+		.inspect(|(key, _)| keys.push(*key));
+
+		// sorted input, such as that produced by serializing a map, is
+		// appended without searching the tree
+		let result = DiscreteRangeMap::from_sorted_iter_strict(elements);
+
+		if let Some(error) = access_error {
+			return Err(error);
 		}
-		Ok(map)
+		result.map_err(|error| overlap_deserialize_error(&keys, error.range))
 	}
 }
 
@@ -1627,6 +4745,10 @@ mod tests {
 	use super::*;
 	use crate::test_ranges::{ee, ei, ie, ii, iu, ue, ui, uu};
 	use crate::utils::{config, contains_point, Config, CutResult};
+	use crate::{
+		Aggregate, DiscreteIntervalMultiMap, DiscreteRangeAggregateMap,
+		DiscreteRangeCounter, DiscreteRangeSet, Monoid,
+	};
 
 	//only every other number to allow mathematical_overlapping_definition
 	//to test between bounds in finite using smaller intervalled finite
@@ -1658,19 +4780,31 @@ mod tests {
 		assert_insert_strict(
 			basic(),
 			(ii(0, 4), false),
-			Err(OverlapError),
+			Err(OverlapError {
+				range: ii(0, 4),
+				overlapping: vec![ui(4)],
+				value: false,
+			}),
 			basic_slice(),
 		);
 		assert_insert_strict(
 			basic(),
 			(ii(5, 6), false),
-			Err(OverlapError),
+			Err(OverlapError {
+				range: ii(5, 6),
+				overlapping: vec![ee(5, 7)],
+				value: false,
+			}),
 			basic_slice(),
 		);
 		assert_insert_strict(
 			basic(),
 			(ii(4, 5), true),
-			Err(OverlapError),
+			Err(OverlapError {
+				range: ii(4, 5),
+				overlapping: vec![ui(4)],
+				value: true,
+			}),
 			basic_slice(),
 		);
 		assert_insert_strict(
@@ -1689,7 +4823,7 @@ mod tests {
 	fn assert_insert_strict<const N: usize>(
 		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
 		to_insert: (InclusiveInterval<i8>, bool),
-		result: Result<(), OverlapError>,
+		result: Result<(), OverlapError<InclusiveInterval<i8>, bool>>,
 		after: [(InclusiveInterval<i8>, bool); N],
 	) {
 		assert_eq!(before.insert_strict(to_insert.0, to_insert.1), result);
@@ -1777,6 +4911,99 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn overlapping_clipped_tests() {
+		assert_eq!(
+			basic().overlapping_clipped(ii(3, 14)).collect::<Vec<_>>(),
+			[
+				(ii(3, 4), &false),
+				(ee(5, 7), &true),
+				(ii(7, 7), &false),
+				(ii(14, 14), &true),
+			]
+		);
+
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			let map =
+				DiscreteRangeMap::from_slice_strict([(first, 1), (second, 2)])
+					.unwrap();
+
+			for range in all_valid_test_bounds().into_iter().chain([uu()]) {
+				let clipped = map
+					.overlapping_clipped(range)
+					.map(|(range, value)| (range, *value))
+					.collect::<Vec<_>>();
+				assert_eq!(clipped, map.clone().cut(range).collect::<Vec<_>>());
+
+				let mut reversed = map
+					.overlapping_clipped(range)
+					.rev()
+					.map(|(range, value)| (range, *value))
+					.collect::<Vec<_>>();
+				reversed.reverse();
+				assert_eq!(clipped, reversed);
+
+				let mut edited = map.clone();
+				let clipped_mut = edited
+					.overlapping_clipped_mut(range)
+					.map(|(range, value)| {
+						*value += 10;
+						(range, *value - 10)
+					})
+					.collect::<Vec<_>>();
+				assert_eq!(clipped, clipped_mut);
+				assert_eq!(
+					edited.into_iter().collect::<Vec<_>>(),
+					map.iter()
+						.map(|(key, value)| {
+							if overlaps(*key, range) {
+								(*key, *value + 10)
+							} else {
+								(*key, *value)
+							}
+						})
+						.collect::<Vec<_>>()
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn get_at_points_tests() {
+		let sorted = (i8::MIN..=i8::MAX).collect::<Vec<_>>();
+		let reversed = sorted.iter().rev().copied().collect::<Vec<_>>();
+		let jumping = sorted
+			.iter()
+			.step_by(3)
+			.chain(sorted.iter().step_by(7))
+			.flat_map(|point| [*point, *point, point / 2])
+			.collect::<Vec<_>>();
+
+		let mut maps = vec![basic(), DiscreteRangeMap::new()];
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			maps.push(
+				DiscreteRangeMap::from_slice_strict([
+					(first, false),
+					(second, true),
+				])
+				.unwrap(),
+			);
+		}
+
+		for map in maps {
+			for points in [&sorted, &reversed, &jumping] {
+				assert_eq!(
+					map.get_at_points(points.iter().copied())
+						.collect::<Vec<_>>(),
+					points
+						.iter()
+						.map(|point| map.get_entry_at_point(*point).ok())
+						.collect::<Vec<_>>()
+				);
+			}
+		}
+	}
+
 	#[test]
 	fn remove_overlapping_tests() {
 		assert_remove_overlapping(basic(), ii(5, 5), [], basic_slice());
@@ -1804,6 +5031,40 @@ mod tests {
 			[(ui(4), false)],
 		);
 	}
+	#[test]
+	fn remove_overlapping_drop_tests() {
+		let mut map = basic();
+		let _ = map.remove_overlapping(ii(6, 7));
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), false),
+				(ie(14, 16), true)
+			])
+			.unwrap()
+		);
+
+		let mut map = basic();
+		let mut removed = map.remove_overlapping(iu(6));
+		assert_eq!(removed.next(), Some((ee(5, 7), true)));
+		drop(removed);
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([(ui(4), false)]).unwrap()
+		);
+
+		let mut map = basic();
+		assert_eq!(map.cut(ii(3, 13)).count(), 3);
+		let _ = map.cut(ii(15, 15));
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ui(2), false),
+				(ii(14, 14), true)
+			])
+			.unwrap()
+		);
+	}
 	fn assert_remove_overlapping<const N: usize, const Y: usize>(
 		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
 		to_remove: InclusiveInterval<i8>,
@@ -1817,6 +5078,109 @@ mod tests {
 		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap())
 	}
 
+	#[test]
+	fn retain_tests() {
+		let mut map = basic();
+		map.retain(|range, value| {
+			*value = !*value;
+			range.start() > 4
+		});
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ee(5, 7), false),
+				(ii(7, 7), true),
+				(ie(14, 16), false),
+			])
+			.unwrap()
+		);
+
+		//only partially consuming the iterator still removes every
+		//matching entry
+		let mut map = basic();
+		let mut extracted = map.extract_if(|_, value| *value);
+		assert_eq!(extracted.next(), Some((ee(5, 7), true)));
+		drop(extracted);
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), false),
+				(ii(7, 7), false),
+			])
+			.unwrap()
+		);
+
+		let mut set = DiscreteRangeSet::from_slice_strict([
+			ii(1, 2),
+			ii(4, 8),
+			ii(10, 10),
+		])
+		.unwrap();
+		assert_eq!(
+			set.extract_if(|range| range.start() > 2)
+				.collect::<Vec<_>>(),
+			[ii(4, 8), ii(10, 10)]
+		);
+		set.retain(|range| range.end() > 5);
+		assert!(set.is_empty());
+	}
+
+	#[test]
+	fn map_values_tests() {
+		assert_eq!(
+			basic().map_values(|range, value| (range.start(), value)),
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), (i8::MIN, false)),
+				(ee(5, 7), (6, true)),
+				(ii(7, 7), (7, false)),
+				(ie(14, 16), (14, true)),
+			])
+			.unwrap()
+		);
+
+		assert_eq!(
+			basic().map_values_coalesce(|_, _| ()),
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), ()),
+				(ii(6, 7), ()),
+				(ie(14, 16), ()),
+			])
+			.unwrap()
+		);
+		assert_eq!(
+			basic().map_values_coalesce(|range, _| range.end() >= 6),
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), false),
+				(ii(6, 7), true),
+				(ie(14, 16), true),
+			])
+			.unwrap()
+		);
+
+		//the result is the same as re-inserting every mapped entry
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			let map =
+				DiscreteRangeMap::from_slice_strict([(first, 1), (second, 2)])
+					.unwrap();
+
+			for modulus in [1, 2] {
+				let mut expected = DiscreteRangeMap::new();
+				for (range, value) in map.clone() {
+					expected
+						.insert_merge_touching_if_values_equal(
+							range,
+							value % modulus,
+						)
+						.unwrap();
+				}
+				assert_eq!(
+					map.clone().map_values_coalesce(|_, value| value % modulus),
+					expected
+				);
+			}
+		}
+	}
+
 	#[test]
 	fn cut_tests() {
 		assert_cut(
@@ -1832,36 +5196,557 @@ mod tests {
 		);
 		assert_cut(
 			basic(),
-			uu(),
+			uu(),
+			[
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 7), false),
+				(ie(14, 16), true),
+			],
+			[],
+		);
+		assert_cut(
+			basic(),
+			ui(6),
+			[(ui(4), false), (ei(5, 6), true)],
+			[(ii(7, 7), false), (ie(14, 16), true)],
+		);
+		assert_cut(
+			basic(),
+			iu(6),
+			[(ie(6, 7), true), (ii(7, 7), false), (ie(14, 16), true)],
+			[(ui(4), false)],
+		);
+	}
+	fn assert_cut<const N: usize, const Y: usize>(
+		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		to_cut: InclusiveInterval<i8>,
+		result: [(InclusiveInterval<i8>, bool); Y],
+		after: [(InclusiveInterval<i8>, bool); N],
+	) {
+		assert_eq!(before.cut(to_cut).collect::<Vec<_>>(), result);
+		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap());
+	}
+
+	#[test]
+	fn cut_with_tests() {
+		// cutting the middle out of a single range splits it twice
+		assert_cut_with(
+			[(ii(0, 10), 1)],
+			ii(4, 6),
+			[ii(0, 10), ii(4, 10)],
+			[(ii(4, 6), 121)],
+			[(ie(0, 4), 11), (ei(6, 10), 122)],
+		);
+		// ranges entirely inside the cut are never split
+		assert_cut_with(
+			[(ie(0, 4), 1), (ii(4, 6), 2), (ei(6, 10), 3)],
+			ii(2, 8),
+			[ie(0, 4), ei(6, 10)],
+			[(ie(2, 4), 12), (ii(4, 6), 2), (ei(6, 8), 31)],
+			[(ie(0, 2), 11), (ei(8, 10), 32)],
+		);
+		assert_cut_with(
+			[(ie(0, 4), 1), (ii(4, 6), 2)],
+			ii(4, 6),
+			[],
+			[(ii(4, 6), 2)],
+			[(ie(0, 4), 1)],
+		);
+	}
+	fn assert_cut_with<
+		const N: usize,
+		const S: usize,
+		const R: usize,
+		const A: usize,
+	>(
+		before: [(InclusiveInterval<i8>, u8); N],
+		to_cut: InclusiveInterval<i8>,
+		splits: [InclusiveInterval<i8>; S],
+		result: [(InclusiveInterval<i8>, u8); R],
+		after: [(InclusiveInterval<i8>, u8); A],
+	) {
+		let mut map = DiscreteRangeMap::from_slice_strict(before).unwrap();
+		let mut split_ranges = Vec::new();
+		let cut = map
+			.cut_with(to_cut, |range, value| {
+				split_ranges.push(*range);
+				(value * 10 + 1, value * 10 + 2)
+			})
+			.collect::<Vec<_>>();
+
+		assert_eq!(cut, result);
+		assert_eq!(split_ranges, splits);
+		assert_eq!(map, DiscreteRangeMap::from_slice_strict(after).unwrap());
+	}
+
+	#[test]
+	fn insert_combine_tests() {
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			let map =
+				DiscreteRangeMap::from_slice_strict([(first, 1), (second, 2)])
+					.unwrap();
+
+			for range in all_valid_test_bounds() {
+				let mut combined = map.clone();
+				combined.insert_combine(range, 5, |old, new| old * 10 + new);
+
+				for point in NUMBERS_DOMAIN {
+					let old = map.get_at_point(*point).copied();
+					let expected = if range.contains(*point) {
+						Some(old.map_or(5, |old| old * 10 + 5))
+					} else {
+						old
+					};
+					assert_eq!(
+						combined.get_at_point(*point).copied(),
+						expected
+					);
+				}
+
+				//the map stays normalized so coalescing only near the
+				//inserted range is enough
+				let mut combined_coalesced = map.clone();
+				combined_coalesced.insert_combine_coalesce(range, 1, |_, _| 1);
+				assert!(combined_coalesced.is_normalized());
+				let mut expected = map.clone();
+				expected.insert_combine(range, 1, |_, _| 1);
+				expected.coalesce();
+				assert_eq!(combined_coalesced, expected);
+			}
+		}
+
+		assert_eq!(
+			basic().try_insert_combine(ii(2, 1), true, |_, _| true),
+			Err(InvalidRangeError { start: 2, end: 1 })
+		);
+	}
+
+	#[test]
+	fn try_tests() {
+		let invalid = ii(6, 2);
+		let error = InvalidRangeError { start: 6, end: 2 };
+		let insert_error =
+			InsertError::InvalidRange(InvalidRangeError { start: 6, end: 2 });
+
+		let mut map = basic();
+		assert_eq!(map.try_overlaps(invalid), Err(error));
+		assert!(map.try_overlapping(invalid).is_err());
+		assert!(map.try_overlapping_mut(invalid).is_err());
+		assert!(map.try_remove_overlapping(invalid).is_err());
+		assert!(map.try_cut(invalid).is_err());
+		assert!(map.try_cut_with(invalid, |_, x| (x, x)).is_err());
+		assert!(map.try_gaps(invalid).is_err());
+		assert!(map.try_contains_range(invalid).is_err());
+		assert!(map.try_overlapping_clipped(invalid).is_err());
+		assert!(map.try_overlapping_clipped_mut(invalid).is_err());
+		assert!(map.try_segments(invalid).is_err());
+		assert_eq!(
+			map.try_covered_len_within(invalid),
+			Err(InvalidRangeError { start: 6, end: 2 })
+		);
+		assert_eq!(
+			map.try_gaps_len(invalid),
+			Err(InvalidRangeError { start: 6, end: 2 })
+		);
+		assert_eq!(map.try_insert_strict(invalid, true), Err(insert_error));
+		assert!(map.try_insert_merge_touching(invalid, true).is_err());
+		assert!(map
+			.try_insert_merge_touching_if_values_equal(invalid, true)
+			.is_err());
+		assert!(map.try_insert_merge_overlapping(invalid, true).is_err());
+		assert!(map
+			.try_insert_merge_touching_or_overlapping(invalid, true)
+			.is_err());
+		assert!(map.try_insert_overwrite(invalid, true).is_err());
+		assert!(map
+			.try_insert_overwrite_with(invalid, true, |_, x| (x, x))
+			.is_err());
+		assert_eq!(map, basic());
+
+		let mut set = DiscreteRangeSet::from_slice_strict([ii(2, 4)]).unwrap();
+		assert!(set.try_overlapping(invalid).is_err());
+		assert!(set.try_cut(invalid).is_err());
+		assert!(set.try_insert_merge_touching(invalid).is_err());
+		assert!(set.try_segments(invalid).is_err());
+		assert!(set.try_complement_within(invalid).is_err());
+		assert_eq!(
+			set.try_gaps_len(invalid),
+			Err(InvalidRangeError { start: 6, end: 2 })
+		);
+		assert_eq!(set.try_insert_strict(ii(5, 6)), Ok(()));
+		assert_eq!(
+			set.try_insert_strict(ii(3, 3)),
+			Err(InsertError::Overlap(OverlapError {
+				range: ii(3, 3),
+				overlapping: vec![ii(2, 4)],
+				value: (),
+			}))
+		);
+		assert_eq!(set.try_insert_merge_touching(ii(7, 7)), Ok(ii(5, 7)));
+
+		let mut counter = DiscreteRangeCounter::new();
+		counter.insert(ii(2, 4));
+		assert_eq!(
+			counter.try_insert(invalid),
+			Err(InvalidRangeError { start: 6, end: 2 })
+		);
+		assert!(counter.try_remove(invalid).is_err());
+		assert!(counter.try_max_depth(invalid).is_err());
+		assert_eq!(counter.try_max_depth(ii(1, 2)), Ok(1));
+		assert_eq!(counter.try_remove(ii(2, 4)), Ok(()));
+		assert!(counter.is_empty());
+
+		let mut aggregate_map =
+			DiscreteRangeAggregateMap::<_, _, _, Entries>::from(basic());
+		assert!(aggregate_map.try_aggregate(invalid).is_err());
+		assert!(aggregate_map.try_cut(invalid).is_err());
+		assert!(aggregate_map.try_remove_overlapping(invalid).is_err());
+		assert!(aggregate_map.try_insert_strict(invalid, true).is_err());
+		assert!(aggregate_map
+			.try_insert_merge_touching(invalid, true)
+			.is_err());
+		assert!(aggregate_map
+			.try_insert_merge_touching_if_values_equal(invalid, true)
+			.is_err());
+		assert!(aggregate_map
+			.try_insert_merge_overlapping(invalid, true)
+			.is_err());
+		assert!(aggregate_map
+			.try_insert_merge_touching_or_overlapping(invalid, true)
+			.is_err());
+		assert!(aggregate_map.try_insert_overwrite(invalid, true).is_err());
+		assert_eq!(aggregate_map.as_map(), &basic());
+		assert_eq!(
+			aggregate_map.try_aggregate(ii(7, 7)).ok(),
+			Some(Entries(vec![(ii(7, 7), false)]))
+		);
+
+		let mut multi_map = DiscreteIntervalMultiMap::new();
+		multi_map.insert(ii(2, 4), ());
+		assert!(multi_map.try_overlapping(invalid).is_err());
+		assert!(multi_map.try_contained_in(invalid).is_err());
+		assert!(multi_map.try_remove_overlapping(invalid).is_err());
+		assert_eq!(multi_map.len(), 1);
+
+		for range in all_valid_test_bounds() {
+			let mut map = basic();
+			assert_eq!(map.try_overlaps(range), Ok(map.overlaps(range)));
+			assert_eq!(
+				map.try_gaps(range).unwrap().collect::<Vec<_>>(),
+				map.gaps(range).collect::<Vec<_>>()
+			);
+			assert_eq!(map.try_gaps_len(range), Ok(map.gaps_len(range)));
+			assert_eq!(
+				map.try_insert_strict(range, false).is_ok(),
+				basic().insert_strict(range, false).is_ok()
+			);
+			let mut expected = basic();
+			expected.insert_overwrite(range, true);
+			let mut map = basic();
+			assert_eq!(map.try_insert_overwrite(range, true), Ok(()));
+			assert_eq!(map, expected);
+		}
+	}
+
+	#[test]
+	fn error_display_tests() {
+		let mut map = basic();
+		let error = map.insert_strict(ii(3, 6), true).unwrap_err();
+		assert_eq!(error.overlapping, [ui(4), ee(5, 7)]);
+		assert_eq!(
+			error.to_string(),
+			"the range InclusiveInterval { start: 3, end: 6 } overlaps the existing ranges [InclusiveInterval { start: -128, end: 4 }, InclusiveInterval { start: 6, end: 6 }]"
+		);
+		assert_eq!(map, basic());
+
+		let error = map.try_insert_strict(ii(6, 2), true).unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			"invalid range with start 6 and end 2, see https://docs.rs/discrete_range_map/latest/discrete_range_map/#invalid-ranges"
+		);
+		assert_eq!(error.source().unwrap().to_string(), error.to_string());
+
+		let error = DiscreteRangeSet::from_slice_strict([ii(2, 4), ii(4, 6)])
+			.unwrap_err();
+		assert_eq!(
+			error,
+			OverlapError {
+				range: ii(4, 6),
+				overlapping: vec![ii(2, 4)],
+				value: ()
+			}
+		);
+	}
+
+	#[test]
+	fn from_sorted_iter_tests() {
+		for first in all_valid_test_bounds() {
+			for second in all_valid_test_bounds() {
+				for third in [ii(0, 0), ii(3, 3), ii(12, 20)] {
+					let entries = [(first, 1), (second, 2), (third, 3)];
+
+					assert_eq!(
+						DiscreteRangeMap::from_sorted_iter_strict(
+							entries.into_iter()
+						),
+						DiscreteRangeMap::from_iter_strict(entries.into_iter())
+					);
+
+					let mut expected = Ok(DiscreteRangeMap::new());
+					for (range, value) in entries {
+						expected = expected.and_then(|mut map| {
+							map.insert_merge_touching(range, value)?;
+							Ok(map)
+						});
+					}
+					assert_eq!(
+						DiscreteRangeMap::from_sorted_iter_merge_touching(
+							entries.into_iter()
+						),
+						expected
+					);
+				}
+			}
+		}
+
+		let set = DiscreteRangeSet::from_sorted_iter_merge_touching(
+			[ii(2, 4), ii(5, 6), ii(8, 8), ii(9, 10)].into_iter(),
+		)
+		.unwrap();
+		assert_eq!(set.into_iter().collect::<Vec<_>>(), [ii(2, 6), ii(8, 10)]);
+	}
+
+	#[test]
+	fn coalesce_tests() {
+		let mut map = basic();
+		assert_eq!(map.is_normalized(), true);
+		map.coalesce();
+		assert_eq!(map, basic());
+
+		let mut map = DiscreteRangeMap::from_slice_strict([
+			(ii(1, 1), 1),
+			(ii(2, 3), 1),
+			(ii(4, 4), 1),
+			(ii(5, 5), 2),
+			(ii(7, 7), 2),
+			(ii(8, 8), 2),
+			(iu(9), 3),
+		])
+		.unwrap();
+		assert_eq!(map.is_normalized(), false);
+		map.coalesce();
+		assert_eq!(map.is_normalized(), true);
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ii(1, 4), 1),
+				(ii(5, 5), 2),
+				(ii(7, 8), 2),
+				(iu(9), 3),
+			])
+			.unwrap()
+		);
+		map.coalesce_by(|_, _| true);
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([(ii(1, 5), 1), (iu(7), 2)])
+				.unwrap()
+		);
+
+		// runs are only removed once `eq` has been called on all of
+		// them, so a panic leaves the rest of the map intact
+		let mut map = DiscreteRangeMap::from_slice_strict([
+			(ii(1, 1), 1),
+			(ii(2, 2), 1),
+			(ii(4, 4), 2),
+			(ii(5, 5), 2),
+		])
+		.unwrap();
+		let mut calls = 0;
+		let result =
+			std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+				map.coalesce_by(|a, b| {
+					calls += 1;
+					if calls == 2 {
+						panic!("eq panicked");
+					}
+					a == b
+				})
+			}));
+		assert!(result.is_err());
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ii(1, 2), 1),
+				(ii(4, 4), 2),
+				(ii(5, 5), 2),
+			])
+			.unwrap()
+		);
+
+		let mut set = DiscreteRangeSet::from_slice_strict([
+			ui(4),
+			ee(4, 7),
+			ii(7, 7),
+			ie(14, 16),
+		])
+		.unwrap();
+		assert_eq!(set.is_normalized(), false);
+		set.coalesce();
+		assert_eq!(set.into_iter().collect::<Vec<_>>(), [ui(7), ie(14, 16)]);
+	}
+
+	#[test]
+	fn split_off_tests() {
+		assert_split_off(basic(), i8::MIN, [], basic_slice());
+		assert_split_off(
+			basic(),
+			0,
+			[(ui(-1), false)],
 			[
-				(ui(4), false),
+				(ii(0, 4), false),
 				(ee(5, 7), true),
 				(ii(7, 7), false),
 				(ie(14, 16), true),
 			],
-			[],
 		);
-		assert_cut(
+		assert_split_off(
 			basic(),
-			ui(6),
-			[(ui(4), false), (ei(5, 6), true)],
+			6,
+			[(ui(4), false)],
+			[(ii(6, 6), true), (ii(7, 7), false), (ie(14, 16), true)],
+		);
+		assert_split_off(
+			basic(),
+			7,
+			[(ui(4), false), (ee(5, 7), true)],
 			[(ii(7, 7), false), (ie(14, 16), true)],
 		);
-		assert_cut(
+		assert_split_off(
 			basic(),
-			iu(6),
-			[(ie(6, 7), true), (ii(7, 7), false), (ie(14, 16), true)],
-			[(ui(4), false)],
+			10,
+			[(ui(4), false), (ee(5, 7), true), (ii(7, 7), false)],
+			[(ie(14, 16), true)],
+		);
+		assert_split_off(
+			basic(),
+			15,
+			[
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 7), false),
+				(ii(14, 14), true),
+			],
+			[(ii(15, 15), true)],
 		);
+		assert_split_off(basic(), i8::MAX, basic_slice(), []);
+
+		for point in -2..=12 {
+			for (first, second) in all_non_overlapping_test_bound_entries() {
+				let map = DiscreteRangeMap::from_slice_strict([
+					(first, 1),
+					(second, 2),
+				])
+				.unwrap();
+
+				let mut lower = map.clone();
+				let upper = lower.split_off(point);
+				assert!(lower.iter().all(|(range, _)| range.end() < point));
+				assert!(upper.iter().all(|(range, _)| range.start() >= point));
+
+				let mut joined = lower.clone();
+				assert_eq!(joined.append(&mut upper.clone()), Ok(()));
+				let mut joined_reverse = upper.clone();
+				assert_eq!(joined_reverse.append(&mut lower.clone()), Ok(()));
+				assert_eq!(joined, joined_reverse);
+				assert_eq!(
+					joined.gaps(uu()).collect::<Vec<_>>(),
+					map.gaps(uu()).collect::<Vec<_>>()
+				);
+			}
+		}
 	}
-	fn assert_cut<const N: usize, const Y: usize>(
+	fn assert_split_off<const N: usize, const Y: usize>(
 		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
-		to_cut: InclusiveInterval<i8>,
-		result: [(InclusiveInterval<i8>, bool); Y],
-		after: [(InclusiveInterval<i8>, bool); N],
+		point: i8,
+		lower: [(InclusiveInterval<i8>, bool); N],
+		upper: [(InclusiveInterval<i8>, bool); Y],
 	) {
-		assert_eq!(before.cut(to_cut).collect::<Vec<_>>(), result);
-		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap());
+		let after = before.split_off(point);
+		assert_eq!(before, DiscreteRangeMap::from_slice_strict(lower).unwrap());
+		assert_eq!(after, DiscreteRangeMap::from_slice_strict(upper).unwrap());
+	}
+
+	#[test]
+	fn append_tests() {
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			let mut map =
+				DiscreteRangeMap::from_slice_strict([(first, 1)]).unwrap();
+			let mut other =
+				DiscreteRangeMap::from_slice_strict([(second, 2)]).unwrap();
+			assert_eq!(map.append(&mut other), Ok(()));
+			assert!(other.is_empty());
+			assert_eq!(
+				map,
+				DiscreteRangeMap::from_slice_strict([(first, 1), (second, 2)])
+					.unwrap()
+			);
+		}
+
+		// interleaving
+		let mut map = DiscreteRangeMap::from_slice_strict([
+			(ii(0, 1), 1),
+			(ii(4, 5), 1),
+			(ii(8, 9), 1),
+		])
+		.unwrap();
+		let mut other =
+			DiscreteRangeMap::from_slice_strict([(ii(2, 3), 2), (ii(6, 7), 2)])
+				.unwrap();
+		assert_eq!(map.append(&mut other), Ok(()));
+		assert_eq!(
+			map.into_iter().collect::<Vec<_>>(),
+			[
+				(ii(0, 1), 1),
+				(ii(2, 3), 2),
+				(ii(4, 5), 1),
+				(ii(6, 7), 2),
+				(ii(8, 9), 1)
+			]
+		);
+
+		// prepending many entries
+		let mut map =
+			DiscreteRangeMap::from_slice_strict([(ii(100, 100), 0)]).unwrap();
+		let mut other = DiscreteRangeMap::from_sorted_iter_strict(
+			(-100..100).map(|x| (ii(x, x), x)),
+		)
+		.unwrap();
+		assert_eq!(map.append(&mut other), Ok(()));
+		assert_eq!(map.len(), 201);
+		assert!(map
+			.iter()
+			.tuple_windows()
+			.all(|((a, _), (b, _))| a.end < b.start));
+
+		// overlapping leaves both maps untouched
+		let mut map = basic();
+		let mut other = DiscreteRangeMap::from_slice_strict([
+			(ii(5, 5), true),
+			(ii(6, 6), true),
+		])
+		.unwrap();
+		assert_eq!(
+			map.append(&mut other),
+			Err(OverlapError {
+				range: ii(6, 6),
+				overlapping: vec![ee(5, 7)],
+				value: ()
+			})
+		);
+		assert_eq!(map, basic());
+		assert_eq!(other.len(), 2);
 	}
 
 	#[test]
@@ -1901,12 +5786,115 @@ mod tests {
 		assert_eq!(map.gaps(outer_range).collect::<Vec<_>>(), result);
 	}
 
+	#[test]
+	fn segments_tests() {
+		assert_eq!(
+			basic().segments(ii(2, 10)).collect::<Vec<_>>(),
+			[
+				Segment::Filled(ii(2, 4), &false),
+				Segment::Gap(ii(5, 5)),
+				Segment::Filled(ee(5, 7), &true),
+				Segment::Filled(ii(7, 7), &false),
+				Segment::Gap(ii(8, 10)),
+			]
+		);
+		assert_eq!(
+			DiscreteRangeMap::<i8, InclusiveInterval<i8>, bool>::new()
+				.segments(uu())
+				.collect::<Vec<_>>(),
+			[Segment::Gap(uu())]
+		);
+		assert_eq!(
+			basic().segments(uu()).rev().collect::<Vec<_>>(),
+			[
+				Segment::Gap(iu(16)),
+				Segment::Filled(ie(14, 16), &true),
+				Segment::Gap(ii(8, 13)),
+				Segment::Filled(ii(7, 7), &false),
+				Segment::Filled(ee(5, 7), &true),
+				Segment::Gap(ii(5, 5)),
+				Segment::Filled(ui(4), &false),
+			]
+		);
+
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			let map =
+				DiscreteRangeMap::from_slice_strict([(first, 1), (second, 2)])
+					.unwrap();
+
+			for range in all_valid_test_bounds().into_iter().chain([uu()]) {
+				let forward = map.segments(range).collect::<Vec<_>>();
+
+				let mut backward =
+					map.segments(range).rev().collect::<Vec<_>>();
+				backward.reverse();
+				assert_eq!(forward, backward);
+
+				//alternating between both ends
+				let mut segments = map.segments(range);
+				let mut front = Vec::new();
+				let mut back = Vec::new();
+				while let Some(segment) = segments.next() {
+					front.push(segment);
+					match segments.next_back() {
+						Some(segment) => back.push(segment),
+						None => break,
+					}
+				}
+				back.reverse();
+				front.extend(back);
+				assert_eq!(forward, front);
+
+				//the segments exactly tile the range
+				let ranges = forward
+					.iter()
+					.map(|(Segment::Filled(range, _) | Segment::Gap(range))| {
+						*range
+					})
+					.collect::<Vec<_>>();
+				assert_eq!(ranges.first().unwrap().start(), range.start());
+				assert_eq!(ranges.last().unwrap().end(), range.end());
+				assert!(ranges
+					.iter()
+					.tuple_windows()
+					.all(|(a, b)| a.end().up() == Some(b.start())));
+
+				assert_eq!(
+					forward
+						.iter()
+						.filter_map(|segment| match segment {
+							Segment::Gap(gap) => Some(*gap),
+							Segment::Filled(..) => None,
+						})
+						.collect::<Vec<_>>(),
+					map.gaps(range).collect::<Vec<_>>()
+				);
+				assert_eq!(
+					forward
+						.iter()
+						.filter_map(|segment| match segment {
+							Segment::Filled(range, value) => {
+								Some((*range, **value))
+							}
+							Segment::Gap(_) => None,
+						})
+						.collect::<Vec<_>>(),
+					map.clone().cut(range).collect::<Vec<_>>()
+				);
+			}
+		}
+	}
+
 	#[test]
 	fn insert_merge_touching_tests() {
 		assert_insert_merge_touching(
 			basic(),
 			(ii(0, 4), false),
-			Err(OverlapError),
+			Err(OverlapError {
+				range: ii(0, 4),
+				overlapping: vec![ui(4)],
+				value: false,
+			}),
 			[
 				(ui(4), false),
 				(ee(5, 7), true),
@@ -1946,7 +5934,10 @@ mod tests {
 	fn assert_insert_merge_touching<const N: usize>(
 		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
 		to_insert: (InclusiveInterval<i8>, bool),
-		result: Result<InclusiveInterval<i8>, OverlapError>,
+		result: Result<
+			InclusiveInterval<i8>,
+			OverlapError<InclusiveInterval<i8>, bool>,
+		>,
 		after: [(InclusiveInterval<i8>, bool); N],
 	) {
 		assert_eq!(
@@ -1960,7 +5951,11 @@ mod tests {
 		assert_insert_merge_touching_if_values_equal(
 			basic(),
 			(ii(0, 4), false),
-			Err(OverlapError),
+			Err(OverlapError {
+				range: ii(0, 4),
+				overlapping: vec![ui(4)],
+				value: false,
+			}),
 			basic_slice(),
 		);
 		dbg!("hererere");
@@ -2002,7 +5997,10 @@ mod tests {
 	fn assert_insert_merge_touching_if_values_equal<const N: usize>(
 		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
 		to_insert: (InclusiveInterval<i8>, bool),
-		result: Result<InclusiveInterval<i8>, OverlapError>,
+		result: Result<
+			InclusiveInterval<i8>,
+			OverlapError<InclusiveInterval<i8>, bool>,
+		>,
 		after: [(InclusiveInterval<i8>, bool); N],
 	) {
 		assert_eq!(
@@ -2045,112 +6043,539 @@ mod tests {
 			ei(5, 11),
 			[(ui(4), false), (ei(5, 11), false), (ie(14, 16), true)],
 		);
-		assert_insert_merge_overlapping(
-			basic(),
-			(ii(15, 18), true),
-			ii(14, 18),
-			[
-				(ui(4), false),
+		assert_insert_merge_overlapping(
+			basic(),
+			(ii(15, 18), true),
+			ii(14, 18),
+			[
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 7), false),
+				(ii(14, 18), true),
+			],
+		);
+		assert_insert_merge_overlapping(
+			basic(),
+			(uu(), false),
+			uu(),
+			[(uu(), false)],
+		);
+	}
+	fn assert_insert_merge_overlapping<const N: usize>(
+		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		to_insert: (InclusiveInterval<i8>, bool),
+		result: InclusiveInterval<i8>,
+		after: [(InclusiveInterval<i8>, bool); N],
+	) {
+		assert_eq!(
+			before.insert_merge_overlapping(to_insert.0, to_insert.1),
+			result
+		);
+		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap())
+	}
+
+	#[test]
+	fn insert_merge_touching_or_overlapping_tests() {
+		assert_insert_merge_touching_or_overlapping(
+			DiscreteRangeMap::from_slice_strict([(ie(1, 4), false)]).unwrap(),
+			(ie(0, 1), true),
+			ie(0, 4),
+			[(ie(0, 4), true)],
+		);
+
+		//copied from insert_merge_overlapping_tests
+		assert_insert_merge_touching_or_overlapping(
+			basic(),
+			(ii(0, 2), true),
+			ui(4),
+			[
+				(ui(4), true),
+				(ee(5, 7), true),
+				(ii(7, 7), false),
+				(ie(14, 16), true),
+			],
+		);
+		assert_insert_merge_touching_or_overlapping(
+			basic(),
+			(ie(14, 16), false),
+			ie(14, 16),
+			[
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 7), false),
+				(ie(14, 16), false),
+			],
+		);
+		assert_insert_merge_touching_or_overlapping(
+			basic(),
+			(ii(6, 11), false),
+			ei(5, 11),
+			[(ui(4), false), (ei(5, 11), false), (ie(14, 16), true)],
+		);
+		assert_insert_merge_touching_or_overlapping(
+			basic(),
+			(ii(15, 18), true),
+			ii(14, 18),
+			[
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 7), false),
+				(ii(14, 18), true),
+			],
+		);
+		assert_insert_merge_touching_or_overlapping(
+			basic(),
+			(uu(), false),
+			uu(),
+			[(uu(), false)],
+		);
+		//the only difference from the insert_merge_overlapping
+		assert_insert_merge_touching_or_overlapping(
+			basic(),
+			(ii(7, 14), false),
+			ee(5, 16),
+			[(ui(4), false), (ee(5, 16), false)],
+		);
+	}
+	fn assert_insert_merge_touching_or_overlapping<const N: usize>(
+		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		to_insert: (InclusiveInterval<i8>, bool),
+		result: InclusiveInterval<i8>,
+		after: [(InclusiveInterval<i8>, bool); N],
+	) {
+		assert_eq!(
+			before
+				.insert_merge_touching_or_overlapping(to_insert.0, to_insert.1),
+			result
+		);
+		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap())
+	}
+
+	#[test]
+	fn set_operation_tests() {
+		let mut sets = vec![DiscreteRangeSet::new()];
+		for range in all_valid_test_bounds() {
+			sets.push(DiscreteRangeSet::from_slice_strict([range]).unwrap());
+		}
+		for (range1, range2) in all_non_overlapping_test_bound_entries() {
+			sets.push(
+				DiscreteRangeSet::from_slice_strict([range1, range2]).unwrap(),
+			);
+		}
+
+		for a in sets.iter() {
+			for b in sets.iter() {
+				assert_set_operation(a, b, a.union(b), |x, y| x || y);
+				assert_set_operation(a, b, a.intersection(b), |x, y| x && y);
+				assert_set_operation(a, b, a.difference(b), |x, y| x && !y);
+				assert_set_operation(
+					a,
+					b,
+					a.symmetric_difference(b),
+					|x, y| x != y,
+				);
+
+				let mut union = a.clone();
+				union.union_with(b);
+				assert_eq!(union, a | b);
+				assert_eq!(
+					union.iter().copied().collect::<Vec<_>>(),
+					a.union(b).collect::<Vec<_>>()
+				);
+
+				let mut intersection = a.clone();
+				intersection.intersection_with(b);
+				assert_eq!(intersection, a & b);
+
+				let mut difference = a.clone();
+				difference.difference_with(b);
+				assert_eq!(difference, a - b);
+
+				let mut symmetric_difference = a.clone();
+				symmetric_difference.symmetric_difference_with(b);
+				assert_eq!(symmetric_difference, a ^ b);
+			}
+		}
+	}
+	#[test]
+	fn complement_tests() {
+		assert_complement([], uu(), [uu()]);
+		assert_complement([uu()], uu(), []);
+		assert_complement([ui(4)], uu(), [iu(5)]);
+		assert_complement([iu(4)], uu(), [ui(3)]);
+		assert_complement(
+			[ui(-100), ii(0, 0), iu(100)],
+			uu(),
+			[ii(-99, -1), ii(1, 99)],
+		);
+		assert_complement([ii(2, 4), ii(5, 6)], uu(), [ui(1), iu(7)]);
+
+		assert_complement([ii(2, 4), ii(8, 8)], ii(3, 8), [ii(5, 7)]);
+		assert_complement([ii(2, 4)], ii(6, 8), [ii(6, 8)]);
+		assert_complement([ui(4)], ui(2), []);
+		assert_complement([ii(2, 4)], iu(3), [iu(5)]);
+
+		for x in NUMBERS {
+			let set =
+				DiscreteRangeSet::from_slice_strict([ii(*x, *x)]).unwrap();
+			assert_eq!(!&!&set, set);
+			assert_eq!(
+				&set | &!&set,
+				DiscreteRangeSet::from_slice_strict([uu()]).unwrap()
+			);
+			assert!((&set & &!&set).is_empty());
+		}
+	}
+	fn assert_complement<const N: usize, const Y: usize>(
+		set: [InclusiveInterval<i8>; N],
+		within: InclusiveInterval<i8>,
+		result: [InclusiveInterval<i8>; Y],
+	) {
+		let set = DiscreteRangeSet::from_slice_strict(set).unwrap();
+		let complement = if within == uu() {
+			set.complement()
+		} else {
+			set.complement_within(within)
+		};
+		assert_eq!(complement.into_iter().collect::<Vec<_>>(), result);
+	}
+
+	fn assert_set_operation(
+		a: &DiscreteRangeSet<i8, InclusiveInterval<i8>>,
+		b: &DiscreteRangeSet<i8, InclusiveInterval<i8>>,
+		result: impl Iterator<Item = InclusiveInterval<i8>>,
+		keep: fn(bool, bool) -> bool,
+	) {
+		let result = result.collect::<Vec<_>>();
+
+		for x in NUMBERS_DOMAIN {
+			let expected = keep(a.contains_point(*x), b.contains_point(*x));
+			let actual = result.iter().any(|range| range.contains(*x));
+
+			if expected != actual {
+				dbg!(a, b, &result, x);
+				panic!("Discrepency in set operation detected!");
+			}
+		}
+		//the resulting ranges must be sorted and maximally-sized
+		for (first, second) in result.iter().tuple_windows() {
+			assert!(first.end().up().unwrap() < second.start());
+		}
+	}
+
+	#[test]
+	fn entry_at_point_tests() {
+		let mut map = basic();
+		match map.entry_at_point(6) {
+			Entry::Occupied(mut entry) => {
+				assert_eq!(entry.range(), &ee(5, 7));
+				assert_eq!(entry.insert(false), true);
+				assert_eq!(entry.remove_entry(), (ee(5, 7), false));
+			}
+			Entry::Vacant(_) => panic!("expected an occupied entry"),
+		}
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), false),
+				(ii(7, 7), false),
+				(ie(14, 16), true),
+			])
+			.unwrap()
+		);
+
+		assert_vacant_entry(basic(), 10, ee(7, 14));
+		assert_vacant_entry(basic(), 5, ii(5, 5));
+		assert_vacant_entry(basic(), i8::MAX, iu(16));
+
+		//inserting part of the gap
+		let mut map = basic();
+		match map.entry_at_point(10) {
+			Entry::Vacant(entry) => {
+				assert_eq!(
+					entry.insert_merge_touching(ii(9, 13), true),
+					Ok(ie(9, 16))
+				);
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		//inserting all of the gap
+		match map.entry_at_point(8) {
+			Entry::Vacant(entry) => {
+				assert_eq!(
+					entry
+						.insert_merge_touching_if_values_equal(ii(8, 8), false),
+					Ok(ii(7, 8))
+				);
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		//extending past the gap
+		match map.entry_at_point(5) {
+			Entry::Vacant(entry) => {
+				assert_eq!(
+					entry.insert_strict(ii(5, 6), false),
+					Err(OverlapError {
+						range: ii(5, 6),
+						overlapping: vec![ii(6, 6)],
+						value: false,
+					})
+				);
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 8), false),
+				(ie(9, 16), true),
+			])
+			.unwrap()
+		);
+
+		//inserting part of the gap without the point
+		let mut map = basic();
+		match map.entry_at_point(10) {
+			Entry::Vacant(entry) => {
+				assert_eq!(entry.insert_strict(ii(12, 13), false), Ok(()));
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		assert_eq!(map.get_entry_at_point(12), Ok((&ii(12, 13), &false)));
+		//merging with both sides of the gap
+		let mut map = basic();
+		match map.entry_at_point(10) {
+			Entry::Vacant(entry) => {
+				assert_eq!(
+					entry.insert_merge_touching(ii(8, 13), true),
+					Ok(ii(7, 15))
+				);
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ui(4), false),
+				(ee(5, 7), true),
+				(ii(7, 15), true),
+			])
+			.unwrap()
+		);
+		//extending past both ends of the gap
+		let mut map = basic();
+		match map.entry_at_point(10) {
+			Entry::Vacant(entry) => {
+				assert_eq!(
+					entry.insert_strict(ii(3, 14), false),
+					Err(OverlapError {
+						range: ii(3, 14),
+						overlapping: vec![
+							ui(4),
+							ii(6, 6),
+							ii(7, 7),
+							ii(14, 15)
+						],
+						value: false,
+					})
+				);
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		assert_eq!(map, basic());
+	}
+	fn assert_vacant_entry(
+		mut map: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		point: i8,
+		gap: InclusiveInterval<i8>,
+	) {
+		match map.entry_at_point(point) {
+			Entry::Vacant(entry) => {
+				assert_eq!(entry.point(), point);
+				assert_eq!(entry.gap(), &gap);
+				assert_eq!(entry.insert_strict(gap, true), Ok(()));
+			}
+			Entry::Occupied(_) => panic!("expected a vacant entry"),
+		}
+		assert_eq!(map.get_entry_at_point(point), Ok((&gap, &true)));
+	}
+
+	#[test]
+	fn cursor_tests() {
+		let segments = [
+			(ui(4), Some(false)),
+			(ii(5, 5), None),
+			(ee(5, 7), Some(true)),
+			(ii(7, 7), Some(false)),
+			(ii(8, 13), None),
+			(ie(14, 16), Some(true)),
+			(iu(16), None),
+		];
+		for point in i8::MIN..=i8::MAX {
+			assert_cursor(basic(), point, &segments);
+		}
+		for point in [i8::MIN, 0, i8::MAX] {
+			assert_cursor(DiscreteRangeMap::new(), point, &[(uu(), None)]);
+		}
+
+		//touching the ends of the domain
+		let map = DiscreteRangeMap::from_slice_strict([
+			(ui(-1), false),
+			(iu(1), true),
+		])
+		.unwrap();
+		for point in i8::MIN..=i8::MAX {
+			assert_cursor(
+				map.clone(),
+				point,
+				&[(ui(-1), Some(false)), (ii(0, 0), None), (iu(1), Some(true))],
+			);
+		}
+
+		//editing
+		let mut map = basic();
+		let mut cursor = map.cursor_mut_at_point(8);
+		assert_eq!(
+			cursor.insert_strict(ii(7, 9), true),
+			Err(OverlapError {
+				range: ii(7, 9),
+				overlapping: vec![ii(7, 7)],
+				value: true,
+			})
+		);
+		assert_eq!(
+			cursor.insert_strict(ii(4, 20), true),
+			Err(OverlapError {
+				range: ii(4, 20),
+				overlapping: vec![ui(4), ee(5, 7), ii(7, 7), ie(14, 16)],
+				value: true,
+			})
+		);
+		assert_eq!(cursor.insert_strict(ii(9, 10), true), Ok(()));
+		assert_eq!(
+			cursor.peek_prev(),
+			Some(CursorItem::Entry(&ii(9, 10), &mut true))
+		);
+		assert_eq!(cursor.next(), Some(CursorItem::Gap(ii(11, 13))));
+		assert_eq!(cursor.remove_next(), Some((ie(14, 16), true)));
+		assert_eq!(cursor.remove_next(), None);
+		assert_eq!(cursor.next(), Some(CursorItem::Gap(iu(11))));
+		assert_eq!(cursor.remove_next(), None);
+		while cursor.prev().is_some() {}
+		assert_eq!(cursor.remove_next(), Some((ui(4), false)));
+		assert_eq!(cursor.peek_prev(), None);
+		assert_eq!(cursor.peek_next(), Some(CursorItem::Gap(ui(5))));
+		assert_eq!(cursor.insert_strict(ii(i8::MIN, i8::MIN), true), Ok(()));
+		assert_eq!(
+			map,
+			DiscreteRangeMap::from_slice_strict([
+				(ii(i8::MIN, i8::MIN), true),
 				(ee(5, 7), true),
 				(ii(7, 7), false),
-				(ii(14, 18), true),
-			],
-		);
-		assert_insert_merge_overlapping(
-			basic(),
-			(uu(), false),
-			uu(),
-			[(uu(), false)],
+				(ii(9, 10), true),
+			])
+			.unwrap()
 		);
 	}
-	fn assert_insert_merge_overlapping<const N: usize>(
-		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
-		to_insert: (InclusiveInterval<i8>, bool),
-		result: InclusiveInterval<i8>,
-		after: [(InclusiveInterval<i8>, bool); N],
+	fn assert_cursor(
+		mut map: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		point: i8,
+		segments: &[(InclusiveInterval<i8>, Option<bool>)],
 	) {
-		assert_eq!(
-			before.insert_merge_overlapping(to_insert.0, to_insert.1),
-			result
-		);
-		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap())
+		fn segment<V: Copy>(
+			item: CursorItem<InclusiveInterval<i8>, &V>,
+		) -> (InclusiveInterval<i8>, Option<V>) {
+			match item {
+				CursorItem::Entry(range, value) => (*range, Some(*value)),
+				CursorItem::Gap(gap) => (gap, None),
+			}
+		}
+		fn segment_mut<V: Copy>(
+			item: CursorItem<InclusiveInterval<i8>, &mut V>,
+		) -> (InclusiveInterval<i8>, Option<V>) {
+			match item {
+				CursorItem::Entry(range, value) => (*range, Some(*value)),
+				CursorItem::Gap(gap) => (gap, None),
+			}
+		}
+
+		let index = segments
+			.iter()
+			.position(|(range, _)| range.contains(point))
+			.unwrap();
+
+		let mut cursor = map.cursor_at_point(point);
+		assert_eq!(cursor.peek_next().map(segment), Some(segments[index]));
+		let mut backward = cursor.clone();
+		let forward = core::iter::from_fn(|| cursor.next().map(segment));
+		assert_eq!(forward.collect::<Vec<_>>(), segments[index..]);
+		let mut backward = core::iter::from_fn(|| backward.prev().map(segment))
+			.collect::<Vec<_>>();
+		backward.reverse();
+		assert_eq!(backward, segments[..index]);
+
+		let mut cursor = map.cursor_mut_at_point(point);
+		assert_eq!(cursor.peek_next().map(segment_mut), Some(segments[index]));
+		let mut forward = Vec::new();
+		while let Some(item) = cursor.next() {
+			forward.push(segment_mut(item));
+		}
+		assert_eq!(forward, segments[index..]);
+		let mut backward = Vec::new();
+		while let Some(item) = cursor.prev() {
+			backward.push(segment_mut(item));
+		}
+		backward.reverse();
+		assert_eq!(backward, segments);
+		assert_eq!(cursor.peek_prev().map(segment_mut), None);
 	}
 
 	#[test]
-	fn insert_merge_touching_or_overlapping_tests() {
-		assert_insert_merge_touching_or_overlapping(
-			DiscreteRangeMap::from_slice_strict([(ie(1, 4), false)]).unwrap(),
-			(ie(0, 1), true),
-			ie(0, 4),
-			[(ie(0, 4), true)],
-		);
-
-		//copied from insert_merge_overlapping_tests
-		assert_insert_merge_touching_or_overlapping(
+	fn merge_with_tests() {
+		assert_merge_with(basic(), DiscreteRangeMap::new(), basic_slice());
+		assert_merge_with(DiscreteRangeMap::new(), basic(), basic_slice());
+		assert_merge_with(
 			basic(),
-			(ii(0, 2), true),
-			ui(4),
-			[
-				(ui(4), true),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ie(14, 16), true),
-			],
-		);
-		assert_insert_merge_touching_or_overlapping(
 			basic(),
-			(ie(14, 16), false),
-			ie(14, 16),
 			[
 				(ui(4), false),
-				(ee(5, 7), true),
+				(ee(5, 7), false),
 				(ii(7, 7), false),
 				(ie(14, 16), false),
 			],
 		);
-		assert_insert_merge_touching_or_overlapping(
-			basic(),
-			(ii(6, 11), false),
-			ei(5, 11),
-			[(ui(4), false), (ei(5, 11), false), (ie(14, 16), true)],
-		);
-		assert_insert_merge_touching_or_overlapping(
+		assert_merge_with(
 			basic(),
-			(ii(15, 18), true),
-			ii(14, 18),
+			DiscreteRangeMap::from_slice_strict([(ii(3, 14), true)]).unwrap(),
 			[
-				(ui(4), false),
-				(ee(5, 7), true),
-				(ii(7, 7), false),
-				(ii(14, 18), true),
+				(ui(2), false),
+				(ii(3, 4), true),
+				(ii(5, 5), true),
+				(ee(5, 7), false),
+				(ii(7, 7), true),
+				(ee(7, 14), true),
+				(ii(14, 14), false),
+				(ee(14, 16), true),
 			],
 		);
-		assert_insert_merge_touching_or_overlapping(
-			basic(),
-			(uu(), false),
-			uu(),
-			[(uu(), false)],
-		);
-		//the only difference from the insert_merge_overlapping
-		assert_insert_merge_touching_or_overlapping(
-			basic(),
-			(ii(7, 14), false),
-			ee(5, 16),
-			[(ui(4), false), (ee(5, 16), false)],
+		assert_merge_with(
+			DiscreteRangeMap::from_slice_strict([(uu(), true)]).unwrap(),
+			DiscreteRangeMap::from_slice_strict([(iu(0), true)]).unwrap(),
+			[(ue(0), true), (iu(0), false)],
 		);
 	}
-	fn assert_insert_merge_touching_or_overlapping<const N: usize>(
-		mut before: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
-		to_insert: (InclusiveInterval<i8>, bool),
-		result: InclusiveInterval<i8>,
-		after: [(InclusiveInterval<i8>, bool); N],
+	fn assert_merge_with<const N: usize>(
+		a: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		b: DiscreteRangeMap<i8, InclusiveInterval<i8>, bool>,
+		result: [(InclusiveInterval<i8>, bool); N],
 	) {
+		// combine with XOR to tell apart overlapping portions
 		assert_eq!(
-			before
-				.insert_merge_touching_or_overlapping(to_insert.0, to_insert.1),
-			result
+			a.merge_with(&b, |x, y| x ^ y),
+			DiscreteRangeMap::from_slice_strict(result).unwrap()
 		);
-		assert_eq!(before, DiscreteRangeMap::from_slice_strict(after).unwrap())
 	}
 
 	#[test]
@@ -2272,6 +6697,658 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn counter_tests() {
+		assert_counter([], [], []);
+		assert_counter([ii(2, 4), ii(2, 4)], [], [(ii(2, 4), 2)]);
+		assert_counter(
+			[ii(2, 6), ii(4, 8), ii(5, 5)],
+			[],
+			[
+				(ii(2, 3), 1),
+				(ii(4, 4), 2),
+				(ii(5, 5), 3),
+				(ii(6, 6), 2),
+				(ii(7, 8), 1),
+			],
+		);
+		assert_counter([ii(2, 4), ii(5, 6)], [], [(ii(2, 6), 1)]);
+		assert_counter([ii(2, 6), ii(4, 8)], [ii(4, 6)], [(ii(2, 8), 1)]);
+		assert_counter([ii(2, 4)], [ii(0, 10), ii(0, 10)], []);
+		assert_counter(
+			[uu(), uu()],
+			[ii(0, 0)],
+			[(ui(-1), 2), (ii(0, 0), 1), (iu(1), 2)],
+		);
+
+		// compare against counting every point by hand
+		for first in all_valid_test_bounds() {
+			for second in all_valid_test_bounds() {
+				let mut counter = DiscreteRangeCounter::new();
+				counter.insert(first);
+				counter.insert(second);
+				counter.remove(ii(5, 7));
+
+				for point in 0..=12 {
+					let expected = [first, second]
+						.iter()
+						.filter(|range| contains_point(**range, point))
+						.count()
+						.saturating_sub(usize::from((5..=7).contains(&point)));
+					assert_eq!(counter.count_at_point(point), expected);
+				}
+				assert!(counter.iter().tuple_windows().all(
+					|((first, first_count), (second, second_count))| {
+						first.end + 1 != second.start
+							|| first_count != second_count
+					}
+				));
+				assert_eq!(
+					counter.max_depth(ii(0, 12)),
+					(0..=12).map(|x| counter.count_at_point(x)).max().unwrap()
+				);
+				assert_eq!(
+					counter.ranges_with_count_at_least(0).collect::<Vec<_>>(),
+					[uu()]
+				);
+			}
+		}
+	}
+	fn assert_counter<const N: usize, const R: usize, const Y: usize>(
+		insert: [InclusiveInterval<i8>; N],
+		remove: [InclusiveInterval<i8>; R],
+		result: [(InclusiveInterval<i8>, usize); Y],
+	) {
+		let mut counter = DiscreteRangeCounter::new();
+		for range in insert {
+			counter.insert(range);
+		}
+		for range in remove {
+			counter.remove(range);
+		}
+		assert_eq!(
+			counter
+				.iter()
+				.map(|(range, count)| (*range, count))
+				.collect::<Vec<_>>(),
+			result
+		);
+	}
+
+	// a non-commutative aggregate so that the order entries are
+	// combined in is tested too
+	#[derive(Debug, Clone, PartialEq)]
+	struct Entries(Vec<(InclusiveInterval<i8>, bool)>);
+	impl Monoid for Entries {
+		fn identity() -> Self {
+			Entries(Vec::new())
+		}
+		fn combine(&self, other: &Self) -> Self {
+			Entries(self.0.iter().chain(other.0.iter()).copied().collect())
+		}
+	}
+	impl Aggregate<InclusiveInterval<i8>, bool> for Entries {
+		fn from_entry(range: InclusiveInterval<i8>, value: &bool) -> Self {
+			Entries(vec![(range, *value)])
+		}
+	}
+
+	#[test]
+	fn aggregate_map_tests() {
+		let mut maps = vec![basic(), DiscreteRangeMap::new()];
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			maps.push(
+				DiscreteRangeMap::from_slice_strict([
+					(first, false),
+					(second, true),
+				])
+				.unwrap(),
+			);
+		}
+
+		type Map =
+			DiscreteRangeAggregateMap<i8, InclusiveInterval<i8>, bool, Entries>;
+		let operations: [fn(&mut Map, InclusiveInterval<i8>); 8] = [
+			|map, range| {
+				let _ = map.cut(range);
+			},
+			|map, range| {
+				let _ = map.remove_overlapping(range);
+			},
+			|map, range| {
+				let _ = map.insert_strict(range, true);
+			},
+			|map, range| {
+				let _ = map.insert_merge_touching(range, true);
+			},
+			|map, range| {
+				let _ = map.insert_merge_touching_if_values_equal(range, true);
+			},
+			|map, range| {
+				map.insert_merge_overlapping(range, true);
+			},
+			|map, range| {
+				map.insert_merge_touching_or_overlapping(range, true);
+			},
+			|map, range| map.insert_overwrite(range, true),
+		];
+
+		for map in maps {
+			let aggregate_map = Map::from(map);
+			assert_aggregates(&aggregate_map);
+
+			for range in all_valid_test_bounds() {
+				for operation in operations {
+					let mut aggregate_map = aggregate_map.clone();
+					operation(&mut aggregate_map, range);
+					assert_aggregates(&aggregate_map);
+				}
+			}
+		}
+	}
+	fn assert_aggregates(
+		map: &DiscreteRangeAggregateMap<
+			i8,
+			InclusiveInterval<i8>,
+			bool,
+			Entries,
+		>,
+	) {
+		for range in all_valid_test_bounds() {
+			assert_eq!(
+				map.aggregate(range),
+				Entries(
+					map.as_map()
+						.overlapping_clipped(range)
+						.map(|(range, value)| (range, *value))
+						.collect()
+				)
+			);
+		}
+	}
+
+	#[test]
+	fn multi_map_tests() {
+		// a deterministic shuffle so the tree isn't built in sorted order
+		let bounds = all_valid_test_bounds();
+		let entries = (0..bounds.len() * 2)
+			.map(|i| (bounds[(i * 7) % bounds.len()], i))
+			.collect::<Vec<_>>();
+
+		let mut map = DiscreteIntervalMultiMap::new();
+		for (range, value) in entries.iter() {
+			map.insert(*range, *value);
+		}
+		assert_eq!(map.len(), entries.len());
+
+		let mut sorted = entries.clone();
+		sorted.sort_by_key(|(range, _)| (range.start, range.end));
+		assert_eq!(
+			map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+			sorted
+		);
+
+		let expected = |predicate: &dyn Fn(&InclusiveInterval<i8>) -> bool| {
+			sorted
+				.iter()
+				.filter(|(range, _)| predicate(range))
+				.copied()
+				.collect::<Vec<_>>()
+		};
+		for range in all_valid_test_bounds() {
+			assert_eq!(
+				map.overlapping(range)
+					.map(|(k, v)| (*k, *v))
+					.collect::<Vec<_>>(),
+				expected(&|other| overlaps(*other, range))
+			);
+			assert_eq!(
+				map.contained_in(range)
+					.map(|(k, v)| (*k, *v))
+					.collect::<Vec<_>>(),
+				expected(&|other| {
+					other.start >= range.start && other.end <= range.end
+				})
+			);
+		}
+		for point in NUMBERS {
+			assert_eq!(
+				map.containing_point(*point)
+					.map(|(k, v)| (*k, *v))
+					.collect::<Vec<_>>(),
+				expected(&|other| contains_point(*other, *point))
+			);
+		}
+
+		for range in all_valid_test_bounds() {
+			let mut map = map.clone();
+			let removed = map.remove_overlapping(range).collect::<Vec<_>>();
+			assert_eq!(removed, expected(&|other| overlaps(*other, range)));
+			assert_eq!(
+				map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+				expected(&|other| !overlaps(*other, range))
+			);
+			assert_eq!(map.len(), sorted.len() - removed.len());
+		}
+
+		// dropping the iterator early still removes the rest
+		let mut partial = map.clone();
+		assert!(partial.remove_overlapping(ii(2, 5)).next().is_some());
+		assert_eq!(
+			partial.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+			expected(&|other| !overlaps(*other, ii(2, 5)))
+		);
+
+		// duplicate ranges come back out in insertion order
+		let mut duplicates = DiscreteIntervalMultiMap::new();
+		for value in 0..7 {
+			duplicates.insert(ii(1, 4), value);
+		}
+		assert_eq!(
+			duplicates
+				.remove_overlapping(ii(2, 2))
+				.map(|(_, value)| value)
+				.collect::<Vec<_>>(),
+			[0, 1, 2, 3, 4, 5, 6]
+		);
+		assert!(duplicates.is_empty());
+
+		assert_eq!(map.clone(), map);
+		assert_eq!(map.clone().into_iter().collect::<Vec<_>>(), sorted);
+		assert!(map.try_insert(ie(4, 4), 0).is_err());
+		assert_eq!(
+			DiscreteIntervalMultiMap::<i8, InclusiveInterval<i8>, ()>::new()
+				.iter()
+				.next(),
+			None
+		);
+	}
+
+	#[test]
+	fn measure_tests() {
+		assert_eq!(u8::distance(u8::MIN, u8::MAX), 255);
+		assert_eq!(i8::distance(i8::MIN, i8::MAX), 255);
+		assert_eq!(i64::distance(-1, 1), 2);
+		assert_eq!(isize::distance(isize::MIN, isize::MAX), usize::MAX as u128);
+		assert_eq!(u128::distance(u128::MIN, u128::MAX), u128::MAX);
+		assert_eq!(i128::distance(i128::MIN, i128::MAX), u128::MAX);
+		assert_eq!(i128::distance(-5, -5), 0);
+		assert_eq!(char::distance('\u{D7FF}', '\u{E000}'), 1);
+		assert_eq!(char::distance('a', 'c'), 2);
+		assert_eq!(char::distance(char::MIN, char::MAX), 0x10FFFF - 0x800);
+		assert_eq!(bool::distance(false, true), 1);
+
+		assert_eq!(ii(4, 4).len(), Some(1));
+		assert_eq!(uu().len(), Some(256));
+		assert_eq!(
+			InclusiveInterval {
+				start: i64::MIN,
+				end: i64::MAX,
+			}
+			.len(),
+			Some(1 << 64)
+		);
+		assert_eq!(
+			InclusiveInterval {
+				start: u128::MIN,
+				end: u128::MAX - 1,
+			}
+			.len(),
+			Some(u128::MAX)
+		);
+		assert_eq!(
+			InclusiveInterval {
+				start: u128::MIN,
+				end: u128::MAX,
+			}
+			.len(),
+			None
+		);
+		assert_eq!(
+			InclusiveInterval {
+				start: i128::MIN,
+				end: i128::MAX,
+			}
+			.len(),
+			None
+		);
+
+		let mut maps = vec![basic(), DiscreteRangeMap::new()];
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			maps.push(
+				DiscreteRangeMap::from_slice_strict([
+					(first, false),
+					(second, true),
+				])
+				.unwrap(),
+			);
+		}
+
+		for map in maps {
+			let count = |range: InclusiveInterval<i8>, covered: bool| {
+				(range.start..=range.end)
+					.filter(|point| map.contains_point(*point) == covered)
+					.count() as u128
+			};
+
+			assert_eq!(map.covered_len(), Some(count(uu(), true)));
+			for range in all_valid_test_bounds() {
+				assert_eq!(
+					map.covered_len_within(range),
+					Some(count(range, true))
+				);
+				assert_eq!(map.gaps_len(range), Some(count(range, false)));
+			}
+		}
+	}
+
+	#[test]
+	fn shift_tests() {
+		for point in i8::MIN..=i8::MAX {
+			let mut up = Some(point);
+			let mut down = Some(point);
+			for n in 0..=300 {
+				assert_eq!(point.up_by(n), up);
+				assert_eq!(point.down_by(n), down);
+				up = up.and_then(DiscreteFinite::up);
+				down = down.and_then(DiscreteFinite::down);
+			}
+		}
+		assert_eq!(i128::MIN.up_by(u128::MAX), Some(i128::MAX));
+		assert_eq!(i128::MAX.down_by(u128::MAX), Some(i128::MIN));
+		assert_eq!(i128::MIN.up_by(u128::MAX).unwrap().up_by(1), None);
+		assert_eq!(u8::MIN.up_by(256), None);
+		assert_eq!('\u{D7FF}'.up_by(1), Some('\u{E000}'));
+		assert_eq!('c'.down_by(2), Some('a'));
+		assert_eq!(false.up_by(1), Some(true));
+		assert_eq!(false.up_by(2), None);
+
+		let mut maps = vec![basic(), DiscreteRangeMap::new()];
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			maps.push(
+				DiscreteRangeMap::from_slice_strict([
+					(first, false),
+					(second, true),
+				])
+				.unwrap(),
+			);
+		}
+
+		let shifted =
+			|point: i8, n: i128| i8::try_from(i128::from(point) + n).ok();
+		for map in maps {
+			for n in [-300, -128, -5, -1, 0, 1, 5, 127, 300] {
+				let mut shifted_all = map.clone();
+				let overflows = map.iter().any(|(range, _)| {
+					shifted(range.start, n).is_none()
+						|| shifted(range.end, n).is_none()
+				});
+				match shifted_all.shift_all(n) {
+					Ok(()) => {
+						assert!(!overflows);
+						assert_eq!(
+							shifted_all.into_iter().collect::<Vec<_>>(),
+							map.iter()
+								.map(|(range, value)| {
+									let range = InclusiveInterval {
+										start: shifted(range.start, n).unwrap(),
+										end: shifted(range.end, n).unwrap(),
+									};
+									(range, *value)
+								})
+								.collect::<Vec<_>>()
+						);
+					}
+					Err(_) => {
+						assert!(overflows);
+						assert_eq!(shifted_all, map);
+					}
+				}
+
+				for range in all_valid_test_bounds() {
+					let mut shifted_range = map.clone();
+					match shifted_range.shift_range(range, n) {
+						Ok(()) => {
+							for point in i8::MIN..=i8::MAX {
+								let source =
+									i8::try_from(i128::from(point) - n)
+										.ok()
+										.filter(|source| {
+											range.contains(*source)
+										});
+								// the gaps in the moved range don't overwrite
+								// anything
+								let expected = source
+									.and_then(|source| map.get_at_point(source))
+									.or(map
+										.get_at_point(point)
+										.filter(|_| !range.contains(point)));
+								assert_eq!(
+									shifted_range.get_at_point(point),
+									expected
+								);
+							}
+						}
+						Err(_) => {
+							assert!(
+								shifted(range.start, n).is_none()
+									|| shifted(range.end, n).is_none()
+							);
+							assert_eq!(shifted_range, map);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn rank_tests() {
+		let mut sets = vec![DiscreteRangeSet::new()];
+		for (first, second) in all_non_overlapping_test_bound_entries() {
+			sets.push(
+				DiscreteRangeSet::from_slice_strict([first, second]).unwrap(),
+			);
+		}
+
+		for set in sets {
+			let points = (i8::MIN..=i8::MAX)
+				.filter(|point| set.contains_point(*point))
+				.collect::<Vec<_>>();
+
+			for k in 0..=256 {
+				assert_eq!(set.nth_point(k), points.get(k as usize).copied());
+			}
+			for point in i8::MIN..=i8::MAX {
+				assert_eq!(
+					set.rank(point),
+					points.iter().filter(|covered| **covered < point).count()
+						as u128
+				);
+			}
+		}
+
+		let full = DiscreteRangeSet::from_slice_strict([InclusiveInterval {
+			start: u128::MIN,
+			end: u128::MAX,
+		}])
+		.unwrap();
+		assert_eq!(full.nth_point(u128::MAX), Some(u128::MAX));
+		assert_eq!(full.rank(u128::MAX), u128::MAX);
+	}
+
+	#[test]
+	fn discrete_finite_edge_tests() {
+		assert_eq!(0_usize.down(), None);
+		assert_eq!(usize::MAX.up(), None);
+		assert_eq!(isize::MIN.down(), None);
+		assert_eq!(isize::MAX.up(), None);
+		assert_eq!((-1_isize).up(), Some(0));
+
+		assert_eq!('\0'.down(), None);
+		assert_eq!(char::MAX.up(), None);
+		assert_eq!('a'.up(), Some('b'));
+		assert_eq!('\u{D7FF}'.up(), Some('\u{E000}'));
+		assert_eq!('\u{E000}'.down(), Some('\u{D7FF}'));
+
+		assert_eq!(false.down(), None);
+		assert_eq!(false.up(), Some(true));
+		assert_eq!(true.down(), Some(false));
+		assert_eq!(true.up(), None);
+
+		assert_edges(0_usize, 2, usize::MAX - 1, usize::MAX);
+		assert_edges(isize::MIN, -2, 2, isize::MAX);
+		assert_edges('\0', '\u{D7FF}', '\u{E000}', char::MAX);
+		assert_edges(false, false, true, true);
+	}
+	// `low_end` and `high_start` may either touch or have a gap between
+	// them, where touching includes skipping over `char` surrogates.
+	fn assert_edges<I>(min: I, low_end: I, high_start: I, max: I)
+	where
+		I: PointType + Debug,
+	{
+		let whole = InclusiveInterval {
+			start: min,
+			end: max,
+		};
+		let low = InclusiveInterval {
+			start: min,
+			end: low_end,
+		};
+		let high = InclusiveInterval {
+			start: high_start,
+			end: max,
+		};
+
+		let mut map = DiscreteRangeMap::new();
+		map.insert_strict(low, ()).unwrap();
+		assert_eq!(
+			map.gaps(whole).collect::<Vec<_>>(),
+			[InclusiveInterval {
+				start: low_end.up().unwrap(),
+				end: max,
+			}]
+		);
+
+		if low_end.up() == Some(high_start) {
+			assert_eq!(map.insert_merge_touching(high, ()), Ok(whole));
+		} else {
+			let gap = InclusiveInterval {
+				start: low_end.up().unwrap(),
+				end: high_start.down().unwrap(),
+			};
+			map.insert_strict(high, ()).unwrap();
+			assert_eq!(map.gaps(whole).collect::<Vec<_>>(), [gap]);
+			assert_eq!(map.insert_merge_touching(gap, ()), Ok(whole));
+		}
+		assert_eq!(map.gaps(whole).collect::<Vec<_>>(), []);
+
+		let middle = InclusiveInterval {
+			start: low_end,
+			end: high_start,
+		};
+		assert_eq!(map.cut(middle).collect::<Vec<_>>(), [(middle, ())]);
+		assert_eq!(map.gaps(whole).collect::<Vec<_>>(), [middle]);
+		assert_eq!(map.insert_merge_touching(middle, ()), Ok(whole));
+	}
+
+	#[cfg(feature = "net")]
+	#[test]
+	fn cidr_tests() {
+		use core::net::{Ipv4Addr, Ipv6Addr};
+
+		use crate::net::ParseCidrError;
+
+		assert_eq!(
+			Ipv4Addr::new(10, 0, 0, 255).up(),
+			Some(Ipv4Addr::new(10, 0, 1, 0))
+		);
+		assert_eq!(Ipv4Addr::MIN.down(), None);
+		assert_eq!(Ipv4Addr::MAX.up(), None);
+		assert_eq!(Ipv6Addr::MIN.down(), None);
+		assert_eq!(Ipv6Addr::MAX.up(), None);
+		assert_edges(
+			Ipv4Addr::MIN,
+			Ipv4Addr::new(127, 255, 255, 255),
+			Ipv4Addr::new(128, 0, 0, 0),
+			Ipv4Addr::MAX,
+		);
+		assert_edges(
+			Ipv6Addr::MIN,
+			Ipv6Addr::from(5),
+			Ipv6Addr::from(9),
+			Ipv6Addr::MAX,
+		);
+
+		let mut set: DiscreteRangeSet<Ipv4Addr, InclusiveInterval<Ipv4Addr>> =
+			DiscreteRangeSet::new();
+		for cidr in [
+			"10.0.0.0",
+			"10.0.0.0/",
+			"10.0.0/8",
+			"10.0.0.0/33",
+			"10.0.0.0/x",
+			"10.0.0.1/31",
+			"10.0.0.0/+8",
+			"10.0.0.0/08",
+			"10.0.0.0/-0",
+		] {
+			assert_eq!(set.insert_cidr(cidr), Err(ParseCidrError));
+		}
+		assert!(set.is_empty());
+
+		set.insert_cidr("0.0.0.0/0").unwrap();
+		assert_eq!(set.to_cidrs().collect::<Vec<_>>(), [(Ipv4Addr::MIN, 0)]);
+
+		let mut set: DiscreteRangeSet<Ipv4Addr, InclusiveInterval<Ipv4Addr>> =
+			DiscreteRangeSet::new();
+		set.insert_cidr("10.0.0.0/25").unwrap();
+		set.insert_cidr("10.0.0.128/25").unwrap();
+		set.insert_cidr("10.0.2.7/32").unwrap();
+		set.insert_cidr("255.255.255.254/31").unwrap();
+		assert_eq!(
+			set.to_cidrs().collect::<Vec<_>>(),
+			[
+				(Ipv4Addr::new(10, 0, 0, 0), 24),
+				(Ipv4Addr::new(10, 0, 2, 7), 32),
+				(Ipv4Addr::new(255, 255, 255, 254), 31),
+			]
+		);
+
+		// every range must decompose into aligned blocks which exactly
+		// cover it
+		for (start, end) in [
+			(0, 0),
+			(1, 6),
+			(3, 17),
+			(0, u32::MAX),
+			(1, u32::MAX),
+			(0, u32::MAX - 1),
+		] {
+			let set =
+				DiscreteRangeSet::from_slice_strict([InclusiveInterval {
+					start: Ipv4Addr::from(start),
+					end: Ipv4Addr::from(end),
+				}])
+				.unwrap();
+			let mut next = u64::from(start);
+			for (block, prefix_length) in set.to_cidrs() {
+				let block = u64::from(u32::from(block));
+				let size = 1_u64 << (32 - prefix_length);
+				assert_eq!(block, next);
+				assert_eq!(block % size, 0);
+				next += size;
+			}
+			assert_eq!(next, u64::from(end) + 1);
+		}
+
+		let mut set: DiscreteRangeSet<Ipv6Addr, InclusiveInterval<Ipv6Addr>> =
+			DiscreteRangeSet::new();
+		set.insert_cidr("::/0").unwrap();
+		assert_eq!(set.to_cidrs().collect::<Vec<_>>(), [(Ipv6Addr::MIN, 0)]);
+		assert_eq!(set.insert_cidr("::1/127"), Err(ParseCidrError));
+	}
+
 	// Test Helper Functions
 	//======================
 	fn all_non_overlapping_test_bound_entries()
@@ -1,14 +1,22 @@
-use core::fmt;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
 use core::marker::PhantomData;
+use core::ops::{BitAnd, BitOr, BitXor, Not, Sub};
 
+use itertools::Itertools;
 use serde::de::{SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::discrete_range_map::{
-	IntoIter as DiscreteRangeMapIntoIter, PointType, RangeType,
+	overlap_deserialize_error, IntoIter as DiscreteRangeMapIntoIter, PointType,
+	RangeType, Segment,
+};
+use crate::utils::{join_touching, sweep};
+use crate::{
+	DiscreteMeasure, DiscreteRangeMap, InclusiveInterval, InsertError,
+	InvalidRangeError, OverlapError, ShiftOverflowError,
 };
-use crate::{DiscreteRangeMap, OverlapError};
 
 /// An ordered set of non-overlapping ranges based on [`DiscreteRangeMap`].
 ///
@@ -48,10 +56,32 @@ where
 	{
 		self.inner.overlapping(range).map(first)
 	}
+	/// See [`DiscreteRangeMap::overlapping_clipped()`] for more details.
+	pub fn overlapping_clipped<'a, Q>(
+		&'a self,
+		range: Q,
+	) -> impl DoubleEndedIterator<Item = K> + '_
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner.overlapping_clipped(range).map(first)
+	}
 	/// See [`DiscreteRangeMap::get_entry_at_point()`] for more details.
 	pub fn get_at_point(&self, point: I) -> Result<&K, K> {
 		self.inner.get_entry_at_point(point).map(first)
 	}
+	/// See [`DiscreteRangeMap::get_at_points()`] for more details.
+	pub fn get_at_points<P>(
+		&self,
+		points: P,
+	) -> impl Iterator<Item = Option<&K>>
+	where
+		P: IntoIterator<Item = I>,
+	{
+		self.inner
+			.get_at_points(points)
+			.map(|entry| entry.map(first))
+	}
 	/// See [`DiscreteRangeMap::contains_point()`] for more details.
 	pub fn contains_point(&self, point: I) -> bool {
 		self.inner.contains_point(point)
@@ -66,6 +96,25 @@ where
 	{
 		self.inner.remove_overlapping(range).map(first)
 	}
+	/// See [`DiscreteRangeMap::retain()`] for more details.
+	pub fn retain<F>(&mut self, mut f: F)
+	where
+		F: FnMut(&K) -> bool,
+	{
+		self.inner.retain(|range, _| f(range))
+	}
+	/// See [`DiscreteRangeMap::extract_if()`] for more details.
+	pub fn extract_if<'a, F>(
+		&'a mut self,
+		mut pred: F,
+	) -> impl Iterator<Item = K> + '_
+	where
+		F: FnMut(&K) -> bool + 'a,
+	{
+		self.inner
+			.extract_if(move |range, _| pred(range))
+			.map(first)
+	}
 	/// See [`DiscreteRangeMap::cut()`] for more details.
 	pub fn cut<'a, Q>(&'a mut self, range: Q) -> impl Iterator<Item = K> + '_
 	where
@@ -80,6 +129,19 @@ where
 	{
 		self.inner.gaps(range)
 	}
+	/// See [`DiscreteRangeMap::segments()`] for more details.
+	pub fn segments<'a, Q>(
+		&'a self,
+		range: Q,
+	) -> impl DoubleEndedIterator<Item = Segment<K, ()>> + '_
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner.segments(range).map(|segment| match segment {
+			Segment::Filled(range, ()) => Segment::Filled(range, ()),
+			Segment::Gap(gap) => Segment::Gap(gap),
+		})
+	}
 	/// See [`DiscreteRangeMap::contains_range()`] for more details.
 	pub fn contains_range<Q>(&self, range: Q) -> bool
 	where
@@ -87,15 +149,41 @@ where
 	{
 		self.inner.contains_range(range)
 	}
+	/// See [`DiscreteRangeMap::covered_len()`] for more details.
+	pub fn covered_len(&self) -> Option<u128>
+	where
+		I: DiscreteMeasure,
+	{
+		self.inner.covered_len()
+	}
+	/// See [`DiscreteRangeMap::covered_len_within()`] for more details.
+	pub fn covered_len_within<Q>(&self, range: Q) -> Option<u128>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		self.inner.covered_len_within(range)
+	}
+	/// See [`DiscreteRangeMap::gaps_len()`] for more details.
+	pub fn gaps_len<Q>(&self, range: Q) -> Option<u128>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		self.inner.gaps_len(range)
+	}
 	/// See [`DiscreteRangeMap::insert_strict()`] for more details.
-	pub fn insert_strict(&mut self, range: K) -> Result<(), OverlapError> {
+	pub fn insert_strict(
+		&mut self,
+		range: K,
+	) -> Result<(), OverlapError<K, ()>> {
 		self.inner.insert_strict(range, ())
 	}
 	/// See [`DiscreteRangeMap::insert_merge_touching()`] for more details.
 	pub fn insert_merge_touching(
 		&mut self,
 		range: K,
-	) -> Result<K, OverlapError> {
+	) -> Result<K, OverlapError<K, ()>> {
 		self.inner.insert_merge_touching(range, ())
 	}
 	/// See [`DiscreteRangeMap::insert_merge_overlapping()`] for more details.
@@ -110,10 +198,162 @@ where
 	pub fn insert_overwrite(&mut self, range: K) {
 		self.inner.insert_overwrite(range, ())
 	}
+	/// See [`DiscreteRangeMap::try_overlaps()`] for more details.
+	pub fn try_overlaps<Q>(
+		&self,
+		range: Q,
+	) -> Result<bool, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		self.inner.try_overlaps(range)
+	}
+	/// See [`DiscreteRangeMap::try_overlapping()`] for more details.
+	pub fn try_overlapping<Q>(
+		&self,
+		range: Q,
+	) -> Result<impl DoubleEndedIterator<Item = &K>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		self.inner
+			.try_overlapping(range)
+			.map(|overlapping| overlapping.map(first))
+	}
+	/// See [`DiscreteRangeMap::try_remove_overlapping()`] for more details.
+	pub fn try_remove_overlapping<'a, Q>(
+		&'a mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = K> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner
+			.try_remove_overlapping(range)
+			.map(|removed| removed.map(first))
+	}
+	/// See [`DiscreteRangeMap::try_cut()`] for more details.
+	pub fn try_cut<'a, Q>(
+		&'a mut self,
+		range: Q,
+	) -> Result<impl Iterator<Item = K> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner.try_cut(range).map(|cut| cut.map(first))
+	}
+	/// See [`DiscreteRangeMap::try_gaps()`] for more details.
+	pub fn try_gaps<'a, Q>(
+		&'a self,
+		range: Q,
+	) -> Result<impl Iterator<Item = K> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner.try_gaps(range)
+	}
+	/// See [`DiscreteRangeMap::try_contains_range()`] for more details.
+	pub fn try_contains_range<Q>(
+		&self,
+		range: Q,
+	) -> Result<bool, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		self.inner.try_contains_range(range)
+	}
+	/// See [`DiscreteRangeMap::try_overlapping_clipped()`] for more details.
+	pub fn try_overlapping_clipped<'a, Q>(
+		&'a self,
+		range: Q,
+	) -> Result<impl DoubleEndedIterator<Item = K> + 'a, InvalidRangeError<I>>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner
+			.try_overlapping_clipped(range)
+			.map(|overlapping| overlapping.map(first))
+	}
+	/// See [`DiscreteRangeMap::try_segments()`] for more details.
+	pub fn try_segments<'a, Q>(
+		&'a self,
+		range: Q,
+	) -> Result<
+		impl DoubleEndedIterator<Item = Segment<K, ()>> + 'a,
+		InvalidRangeError<I>,
+	>
+	where
+		Q: RangeType<I> + 'a,
+	{
+		self.inner.try_segments(range).map(|segments| {
+			segments.map(|segment| match segment {
+				Segment::Filled(range, ()) => Segment::Filled(range, ()),
+				Segment::Gap(gap) => Segment::Gap(gap),
+			})
+		})
+	}
+	/// See [`DiscreteRangeMap::try_covered_len_within()`] for more details.
+	pub fn try_covered_len_within<Q>(
+		&self,
+		range: Q,
+	) -> Result<Option<u128>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		self.inner.try_covered_len_within(range)
+	}
+	/// See [`DiscreteRangeMap::try_gaps_len()`] for more details.
+	pub fn try_gaps_len<Q>(
+		&self,
+		range: Q,
+	) -> Result<Option<u128>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+		I: DiscreteMeasure,
+	{
+		self.inner.try_gaps_len(range)
+	}
+	/// See [`DiscreteRangeMap::try_insert_strict()`] for more details.
+	pub fn try_insert_strict(
+		&mut self,
+		range: K,
+	) -> Result<(), InsertError<I, K, ()>> {
+		self.inner.try_insert_strict(range, ())
+	}
+	/// See [`DiscreteRangeMap::try_insert_merge_touching()`] for more details.
+	pub fn try_insert_merge_touching(
+		&mut self,
+		range: K,
+	) -> Result<K, InsertError<I, K, ()>> {
+		self.inner.try_insert_merge_touching(range, ())
+	}
+	/// See [`DiscreteRangeMap::try_insert_merge_overlapping()`] for more details.
+	pub fn try_insert_merge_overlapping(
+		&mut self,
+		range: K,
+	) -> Result<K, InvalidRangeError<I>> {
+		self.inner.try_insert_merge_overlapping(range, ())
+	}
+	/// See [`DiscreteRangeMap::try_insert_merge_touching_or_overlapping()`] for more details.
+	pub fn try_insert_merge_touching_or_overlapping(
+		&mut self,
+		range: K,
+	) -> Result<K, InvalidRangeError<I>> {
+		self.inner
+			.try_insert_merge_touching_or_overlapping(range, ())
+	}
+	/// See [`DiscreteRangeMap::try_insert_overwrite()`] for more details.
+	pub fn try_insert_overwrite(
+		&mut self,
+		range: K,
+	) -> Result<(), InvalidRangeError<I>> {
+		self.inner.try_insert_overwrite(range, ())
+	}
 	/// See [`DiscreteRangeMap::from_slice_strict()`] for more details.
 	pub fn from_slice_strict<const N: usize>(
 		slice: [K; N],
-	) -> Result<DiscreteRangeSet<I, K>, OverlapError> {
+	) -> Result<DiscreteRangeSet<I, K>, OverlapError<K, ()>> {
 		let mut set = DiscreteRangeSet::new();
 		for range in slice {
 			set.insert_strict(range)?;
@@ -123,13 +363,422 @@ where
 	/// See [`DiscreteRangeMap::from_iter_strict()`] for more details.
 	pub fn from_iter_strict(
 		iter: impl Iterator<Item = K>,
-	) -> Result<DiscreteRangeSet<I, K>, OverlapError> {
+	) -> Result<DiscreteRangeSet<I, K>, OverlapError<K, ()>> {
 		let mut set = DiscreteRangeSet::new();
 		for range in iter {
 			set.insert_strict(range)?;
 		}
 		return Ok(set);
 	}
+	/// See [`DiscreteRangeMap::from_sorted_iter_strict()`] for more details.
+	pub fn from_sorted_iter_strict(
+		iter: impl Iterator<Item = K>,
+	) -> Result<DiscreteRangeSet<I, K>, OverlapError<K, ()>> {
+		Ok(DiscreteRangeSet {
+			inner: DiscreteRangeMap::from_sorted_iter_strict(
+				iter.map(|range| (range, ())),
+			)?,
+		})
+	}
+	/// See [`DiscreteRangeMap::from_sorted_iter_merge_touching()`] for more details.
+	pub fn from_sorted_iter_merge_touching(
+		iter: impl Iterator<Item = K>,
+	) -> Result<DiscreteRangeSet<I, K>, OverlapError<K, ()>> {
+		Ok(DiscreteRangeSet {
+			inner: DiscreteRangeMap::from_sorted_iter_merge_touching(
+				iter.map(|range| (range, ())),
+			)?,
+		})
+	}
+	/// See [`DiscreteRangeMap::shift_all()`] for more details.
+	pub fn shift_all(&mut self, n: i128) -> Result<(), ShiftOverflowError<I>> {
+		self.inner.shift_all(n)
+	}
+	/// See [`DiscreteRangeMap::shift_range()`] for more details.
+	pub fn shift_range<Q>(
+		&mut self,
+		range: Q,
+		n: i128,
+	) -> Result<(), ShiftOverflowError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		self.inner.shift_range(range, n)
+	}
+	/// See [`DiscreteRangeMap::split_off()`] for more details.
+	pub fn split_off(&mut self, point: I) -> DiscreteRangeSet<I, K> {
+		DiscreteRangeSet {
+			inner: self.inner.split_off(point),
+		}
+	}
+	/// See [`DiscreteRangeMap::append()`] for more details.
+	pub fn append(
+		&mut self,
+		other: &mut Self,
+	) -> Result<(), OverlapError<K, ()>> {
+		self.inner.append(&mut other.inner)
+	}
+	/// Merges every run of touching ranges in the set into a single
+	/// range.
+	///
+	/// See [`DiscreteRangeMap::coalesce()`] for more details.
+	pub fn coalesce(&mut self) {
+		self.inner.coalesce()
+	}
+	/// Returns `true` if no two ranges in the set touch, and `false`
+	/// if any do.
+	///
+	/// See [`DiscreteRangeMap::is_normalized()`] for more details.
+	pub fn is_normalized(&self) -> bool {
+		self.inner.is_normalized()
+	}
+
+	/// Returns an iterator over the ranges covering every point that is
+	/// in `self` or `other` in ascending order.
+	///
+	/// Touching and overlapping ranges are merged together so the
+	/// returned ranges are maximally-sized. This runs in linear time over
+	/// the ranges of both sets.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let a =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(1, 4), ii(8, 10)])
+	/// 		.unwrap();
+	/// let b =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 6), ii(11, 12)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(
+	/// 	a.union(&b).collect::<Vec<_>>(),
+	/// 	[ii(1, 6), ii(8, 12)]
+	/// );
+	/// ```
+	pub fn union<'a>(
+		&'a self,
+		other: &'a Self,
+	) -> impl Iterator<Item = K> + 'a {
+		self.set_operation(other, |in_self, in_other| in_self || in_other)
+	}
+	/// Returns an iterator over the ranges covering every point that is
+	/// in both `self` and `other` in ascending order.
+	///
+	/// Touching ranges are merged together so the returned ranges are
+	/// maximally-sized. This runs in linear time over the ranges of both
+	/// sets.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let a =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(1, 4), ii(8, 10)])
+	/// 		.unwrap();
+	/// let b = DiscreteRangeSet::from_slice_strict([ii(3, 9)]).unwrap();
+	///
+	/// assert_eq!(
+	/// 	a.intersection(&b).collect::<Vec<_>>(),
+	/// 	[ii(3, 4), ii(8, 9)]
+	/// );
+	/// ```
+	pub fn intersection<'a>(
+		&'a self,
+		other: &'a Self,
+	) -> impl Iterator<Item = K> + 'a {
+		self.set_operation(other, |in_self, in_other| in_self && in_other)
+	}
+	/// Returns an iterator over the ranges covering every point that is
+	/// in `self` but not in `other` in ascending order.
+	///
+	/// Touching ranges are merged together so the returned ranges are
+	/// maximally-sized. This runs in linear time over the ranges of both
+	/// sets.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let a = DiscreteRangeSet::from_slice_strict([ii(1, 10)]).unwrap();
+	/// let b =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 4), ii(8, 12)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(
+	/// 	a.difference(&b).collect::<Vec<_>>(),
+	/// 	[ii(1, 2), ii(5, 7)]
+	/// );
+	/// ```
+	pub fn difference<'a>(
+		&'a self,
+		other: &'a Self,
+	) -> impl Iterator<Item = K> + 'a {
+		self.set_operation(other, |in_self, in_other| in_self && !in_other)
+	}
+	/// Returns an iterator over the ranges covering every point that is
+	/// in exactly one of `self` and `other` in ascending order.
+	///
+	/// Touching ranges are merged together so the returned ranges are
+	/// maximally-sized. This runs in linear time over the ranges of both
+	/// sets.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let a = DiscreteRangeSet::from_slice_strict([ii(1, 6)]).unwrap();
+	/// let b = DiscreteRangeSet::from_slice_strict([ii(4, 8)]).unwrap();
+	///
+	/// assert_eq!(
+	/// 	a.symmetric_difference(&b).collect::<Vec<_>>(),
+	/// 	[ii(1, 3), ii(7, 8)]
+	/// );
+	/// ```
+	pub fn symmetric_difference<'a>(
+		&'a self,
+		other: &'a Self,
+	) -> impl Iterator<Item = K> + 'a {
+		self.set_operation(other, |in_self, in_other| in_self != in_other)
+	}
+	fn set_operation<'a>(
+		&'a self,
+		other: &'a Self,
+		keep: fn(bool, bool) -> bool,
+	) -> impl Iterator<Item = K> + 'a {
+		sweep(
+			self.inner.iter().map(key_only),
+			other.inner.iter().map(key_only),
+		)
+		.filter(move |(_, in_self, in_other)| {
+			keep(in_self.is_some(), in_other.is_some())
+		})
+		.map(|(range, _, _)| range)
+		.coalesce(join_touching)
+		.map(K::from)
+	}
+
+	/// Replaces `self` with the union of `self` and `other`.
+	///
+	/// See [`DiscreteRangeSet::union()`] for more details.
+	pub fn union_with(&mut self, other: &Self) {
+		*self = DiscreteRangeSet::from_sorted_unchecked(self.union(other));
+	}
+	/// Replaces `self` with the intersection of `self` and `other`.
+	///
+	/// See [`DiscreteRangeSet::intersection()`] for more details.
+	pub fn intersection_with(&mut self, other: &Self) {
+		*self =
+			DiscreteRangeSet::from_sorted_unchecked(self.intersection(other));
+	}
+	/// Replaces `self` with the difference of `self` and `other`.
+	///
+	/// See [`DiscreteRangeSet::difference()`] for more details.
+	pub fn difference_with(&mut self, other: &Self) {
+		*self = DiscreteRangeSet::from_sorted_unchecked(self.difference(other));
+	}
+	/// Replaces `self` with the symmetric difference of `self` and
+	/// `other`.
+	///
+	/// See [`DiscreteRangeSet::symmetric_difference()`] for more details.
+	pub fn symmetric_difference_with(&mut self, other: &Self) {
+		*self = DiscreteRangeSet::from_sorted_unchecked(
+			self.symmetric_difference(other),
+		);
+	}
+
+	/// Returns a new set covering every point in the given range that
+	/// is not covered by `self`.
+	///
+	/// This is equivalent to collecting [`DiscreteRangeSet::gaps()`]
+	/// into a new set.
+	///
+	/// # Panics
+	///
+	/// Panics if the given range is an invalid range. See [`Invalid
+	/// Ranges`](https://docs.rs/discrete_range_map/latest/discrete_range_map/index.html#invalid-ranges)
+	/// for more details.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let set =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 5), ii(8, 9)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(
+	/// 	set.complement_within(ii(0, 10))
+	/// 		.into_iter()
+	/// 		.collect::<Vec<_>>(),
+	/// 	[ii(0, 2), ii(6, 7), ii(10, 10)]
+	/// );
+	/// ```
+	pub fn complement_within<Q>(&self, range: Q) -> DiscreteRangeSet<I, K>
+	where
+		Q: RangeType<I>,
+	{
+		DiscreteRangeSet::from_sorted_unchecked(self.gaps(range))
+	}
+	/// Fallible version of [`DiscreteRangeSet::complement_within()`].
+	///
+	/// Returns an [`InvalidRangeError`] if the given range is an
+	/// invalid range instead of panicking.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::{DiscreteRangeSet, InvalidRangeError};
+	///
+	/// let set =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 5)]).unwrap();
+	///
+	/// assert_eq!(
+	/// 	set.try_complement_within(ii(10, 0)),
+	/// 	Err(InvalidRangeError { start: 10, end: 0 })
+	/// );
+	/// ```
+	pub fn try_complement_within<Q>(
+		&self,
+		range: Q,
+	) -> Result<DiscreteRangeSet<I, K>, InvalidRangeError<I>>
+	where
+		Q: RangeType<I>,
+	{
+		self.try_gaps(range)
+			.map(DiscreteRangeSet::from_sorted_unchecked)
+	}
+	/// Returns a new set covering every point of `I` that is not covered
+	/// by `self`, from [`DiscreteFinite::MIN`] to
+	/// [`DiscreteFinite::MAX`] inclusive.
+	///
+	/// [`DiscreteFinite::MIN`]: crate::DiscreteFinite::MIN
+	/// [`DiscreteFinite::MAX`]: crate::DiscreteFinite::MAX
+	///
+	/// This is also available via the `!` operator on
+	/// `&DiscreteRangeSet`.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::{ii, iu, ui};
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let set =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 5), ii(8, 9)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(
+	/// 	set.complement().into_iter().collect::<Vec<_>>(),
+	/// 	[ui(2), ii(6, 7), iu(10)]
+	/// );
+	/// assert_eq!(!&set.complement(), set);
+	/// ```
+	pub fn complement(&self) -> DiscreteRangeSet<I, K> {
+		self.complement_within(InclusiveInterval {
+			start: I::MIN,
+			end: I::MAX,
+		})
+	}
+	/// Returns the `k`th point covered by the set, counting from zero,
+	/// or `None` if the set covers `k` points or fewer.
+	///
+	/// This walks the ranges in ascending order, taking `O(n)` time
+	/// where `n` is the number of ranges before the returned point.
+	///
+	/// Taking `O(log n)` time would need the number of points covered
+	/// by each subtree, kept in a separate augmented tree like the one
+	/// behind
+	/// [`DiscreteRangeAggregateMap`](crate::DiscreteRangeAggregateMap).
+	/// The set doesn't keep such an index since every insertion and
+	/// removal would then pay to update it, including for users who
+	/// never ask for the `k`th point.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let set =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 5), ii(8, 9)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(set.nth_point(0), Some(3));
+	/// assert_eq!(set.nth_point(3), Some(8));
+	/// assert_eq!(set.nth_point(4), Some(9));
+	/// assert_eq!(set.nth_point(5), None);
+	/// ```
+	pub fn nth_point(&self, mut k: u128) -> Option<I>
+	where
+		I: DiscreteMeasure,
+	{
+		for range in self.iter() {
+			match range.len() {
+				Some(len) if k >= len => k -= len,
+				// a range without a `len()` covers every point so it
+				// contains the `k`th point for any `k`
+				_ => return range.start().up_by(k),
+			}
+		}
+
+		return None;
+	}
+	/// Returns the number of points covered by the set which are less
+	/// than the given point.
+	///
+	/// This can't overflow since there are fewer than `2^128` points
+	/// less than any point.
+	///
+	/// This takes `O(log n + k)` time where `k` is the number of ranges
+	/// before the given point. See [`DiscreteRangeSet::nth_point()`]
+	/// for why the set doesn't keep an index to do this in `O(log n)`
+	/// time. If you need that, use a
+	/// [`DiscreteRangeAggregateMap`](crate::DiscreteRangeAggregateMap)
+	/// whose [`Aggregate`](crate::Aggregate) is the number of points in
+	/// each range and aggregate the range up to the point.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ii;
+	/// use discrete_range_map::DiscreteRangeSet;
+	///
+	/// let set =
+	/// 	DiscreteRangeSet::from_slice_strict([ii(3, 5), ii(8, 9)])
+	/// 		.unwrap();
+	///
+	/// assert_eq!(set.rank(0), 0);
+	/// assert_eq!(set.rank(4), 1);
+	/// assert_eq!(set.rank(8), 3);
+	/// assert_eq!(set.rank(100), 5);
+	/// ```
+	pub fn rank(&self, point: I) -> u128
+	where
+		I: DiscreteMeasure,
+	{
+		match point.down() {
+			Some(below) => self
+				.covered_len_within(InclusiveInterval {
+					start: I::MIN,
+					end: below,
+				})
+				.unwrap(),
+			None => 0,
+		}
+	}
+
+	fn from_sorted_unchecked(
+		ranges: impl Iterator<Item = K>,
+	) -> DiscreteRangeSet<I, K> {
+		DiscreteRangeSet {
+			inner: DiscreteRangeMap::from_sorted_iter_unchecked(
+				ranges.map(|range| (range, ())),
+			),
+		}
+	}
 }
 
 impl<I, K> DiscreteRangeSet<I, K> {
@@ -167,6 +816,13 @@ fn first<A, B>((a, _): (A, B)) -> A {
 	a
 }
 
+fn key_only<K>((key, _): (&K, &())) -> (K, ())
+where
+	K: Copy,
+{
+	(*key, ())
+}
+
 // Trait Impls ==========================
 
 impl<I, K> IntoIterator for DiscreteRangeSet<I, K> {
@@ -196,6 +852,81 @@ impl<I, K> Iterator for IntoIter<I, K> {
 	}
 }
 
+impl<I, K> BitOr<&DiscreteRangeSet<I, K>> for &DiscreteRangeSet<I, K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Output = DiscreteRangeSet<I, K>;
+
+	/// Returns the union of `self` and `rhs` as a new `DiscreteRangeSet`.
+	///
+	/// See [`DiscreteRangeSet::union()`] for more details.
+	fn bitor(self, rhs: &DiscreteRangeSet<I, K>) -> Self::Output {
+		DiscreteRangeSet::from_sorted_unchecked(self.union(rhs))
+	}
+}
+impl<I, K> BitAnd<&DiscreteRangeSet<I, K>> for &DiscreteRangeSet<I, K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Output = DiscreteRangeSet<I, K>;
+
+	/// Returns the intersection of `self` and `rhs` as a new
+	/// `DiscreteRangeSet`.
+	///
+	/// See [`DiscreteRangeSet::intersection()`] for more details.
+	fn bitand(self, rhs: &DiscreteRangeSet<I, K>) -> Self::Output {
+		DiscreteRangeSet::from_sorted_unchecked(self.intersection(rhs))
+	}
+}
+impl<I, K> Sub<&DiscreteRangeSet<I, K>> for &DiscreteRangeSet<I, K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Output = DiscreteRangeSet<I, K>;
+
+	/// Returns the difference of `self` and `rhs` as a new
+	/// `DiscreteRangeSet`.
+	///
+	/// See [`DiscreteRangeSet::difference()`] for more details.
+	fn sub(self, rhs: &DiscreteRangeSet<I, K>) -> Self::Output {
+		DiscreteRangeSet::from_sorted_unchecked(self.difference(rhs))
+	}
+}
+impl<I, K> BitXor<&DiscreteRangeSet<I, K>> for &DiscreteRangeSet<I, K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Output = DiscreteRangeSet<I, K>;
+
+	/// Returns the symmetric difference of `self` and `rhs` as a new
+	/// `DiscreteRangeSet`.
+	///
+	/// See [`DiscreteRangeSet::symmetric_difference()`] for more details.
+	fn bitxor(self, rhs: &DiscreteRangeSet<I, K>) -> Self::Output {
+		DiscreteRangeSet::from_sorted_unchecked(self.symmetric_difference(rhs))
+	}
+}
+
+impl<I, K> Not for &DiscreteRangeSet<I, K>
+where
+	I: PointType,
+	K: RangeType<I>,
+{
+	type Output = DiscreteRangeSet<I, K>;
+
+	/// Returns the complement of `self` as a new `DiscreteRangeSet`.
+	///
+	/// See [`DiscreteRangeSet::complement()`] for more details.
+	fn not(self) -> Self::Output {
+		self.complement()
+	}
+}
+
 impl<I, K> Default for DiscreteRangeSet<I, K>
 where
 	I: PointType,
@@ -259,11 +990,27 @@ where
 	where
 		A: SeqAccess<'de>,
 	{
-		let mut set = DiscreteRangeSet::new();
-		while let Some(range_bounds) = access.next_element()? {
-			set.insert_strict(range_bounds)
-				.map_err(|_| serde::de::Error::custom("ranges overlap"))?;
+		let mut access_error = None;
+		// see the `DiscreteRangeMap` visitor
+		let mut keys = Vec::new();
+		// fused so that `access` isn't polled again after an error
+		let elements = core::iter::from_fn(|| {
+			access
+				.next_element()
+				.map_err(|error| access_error = Some(error))
+				.ok()
+				.flatten()
+		})
+		.fuse()
+		.inspect(|key| keys.push(*key));
+
+		// sorted input, such as that produced by serializing a set, is
+		// appended without searching the tree
+		let result = DiscreteRangeSet::from_sorted_iter_strict(elements);
+
+		if let Some(error) = access_error {
+			return Err(error);
 		}
-		Ok(set)
+		result.map_err(|error| overlap_deserialize_error(&keys, error.range))
 	}
 }
@@ -21,7 +21,11 @@ use core::ops::{Bound, RangeBounds};
 
 use serde::{Deserialize, Serialize};
 
-use crate::discrete_range_map::{InclusiveRange, PointType};
+use crate::discrete_range_map::{
+	InclusiveRange, PointType, ShiftOverflowError,
+};
+#[cfg(doc)]
+use crate::DiscreteFinite;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct InclusiveInterval<I> {
@@ -29,7 +33,52 @@ pub struct InclusiveInterval<I> {
 	pub end: I,
 }
 
-impl<I> InclusiveInterval<I> where I: PointType {}
+impl<I> InclusiveInterval<I>
+where
+	I: PointType,
+{
+	/// Returns the interval moved up by `n` points if `n` is positive or
+	/// down by `-n` points if `n` is negative.
+	///
+	/// If either end of the interval would overflow past
+	/// [`DiscreteFinite::MIN`] or [`DiscreteFinite::MAX`] then a
+	/// [`ShiftOverflowError`] is returned instead.
+	///
+	/// # Examples
+	/// ```
+	/// use discrete_range_map::test_ranges::ie;
+	/// use discrete_range_map::{InclusiveInterval, ShiftOverflowError};
+	///
+	/// assert_eq!(ie(1, 4).shift(10), Ok(ie(11, 14)));
+	/// assert_eq!(ie(1, 4).shift(-1), Ok(ie(0, 3)));
+	/// assert_eq!(
+	/// 	ie(1, 4).shift(125),
+	/// 	Err(ShiftOverflowError {
+	/// 		start: 1,
+	/// 		end: 3,
+	/// 		n: 125
+	/// 	})
+	/// );
+	/// ```
+	pub fn shift(self, n: i128) -> Result<Self, ShiftOverflowError<I>> {
+		let shift = |point: I| {
+			if n >= 0 {
+				point.up_by(n.unsigned_abs())
+			} else {
+				point.down_by(n.unsigned_abs())
+			}
+		};
+
+		match (shift(self.start), shift(self.end)) {
+			(Some(start), Some(end)) => Ok(InclusiveInterval { start, end }),
+			_ => Err(ShiftOverflowError {
+				start: self.start,
+				end: self.end,
+				n,
+			}),
+		}
+	}
+}
 
 impl<I> RangeBounds<I> for InclusiveInterval<I>
 where
@@ -168,6 +168,13 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 //! | (Bound::Exluded(3), Bound::Exluded(4)) | NO    |
 //! | 400..=400                              | YES   |
 //!
+//! Functions given an invalid range will panic, but most also have a
+//! fallible `try_` variant, such as `try_insert_strict()`, which
+//! returns an `InvalidRangeError` instead, useful when ranges come
+//! from user input. `shift_range()` has no `try_` variant as it
+//! already returns a `Result` for overflow, so check the range with
+//! `InclusiveRange::is_valid()` first if it may be invalid.
+//!
 //! ### Overlap
 //!
 //! Two ranges are "overlapping" if there exists a point that is contained
@@ -189,7 +196,9 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 //! <https://en.wikipedia.org/wiki/Interval_(mathematics)>
 //!
 //! # Features
-//! This crate currently has no features.
+//! - `net`: implements [`DiscreteFinite`] for [`Ipv4Addr`] and [`Ipv6Addr`]
+//!   and adds CIDR helpers to [`DiscreteRangeSet`]s of them, see the
+//!   `net` module for more details.
 //!
 //! # Credit
 //!
@@ -240,7 +249,8 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 //!   A data structure based off of a 2007 published paper! It supports
 //!   any range as keys, unfortunately, it is implemented with a
 //!   non-balancing `Box<Node>` based tree, however it also supports
-//!   overlapping ranges which my library does not.
+//!   overlapping ranges which my library only supports via the
+//!   separate [`DiscreteIntervalMultiMap`].
 //! - <https://docs.rs/rangetree>
 //!   I'm not entirely sure what this library is or isn't, but it looks like
 //!   a custom red-black tree/BTree implementation used specifically for a
@@ -256,6 +266,8 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 //! [`rangeinclusivemap`]: https://docs.rs/rangemap/latest/rangemap/inclusive_map/struct.RangeInclusiveMap.html#
 //! [`rangeinclusive`]: https://doc.rust-lang.org/std/ops/struct.RangeInclusive.html
 //! [`ord`]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
+//! [`ipv4addr`]: https://doc.rust-lang.org/core/net/struct.Ipv4Addr.html
+//! [`ipv6addr`]: https://doc.rust-lang.org/core/net/struct.Ipv6Addr.html
 //! [`discreteboundsmap`]: https://docs.rs/discrete_range_map/latest/discrete_range_map/discrete_range_map/struct.DiscreteRangeMap.html
 //! [`discreteboundsset`]: https://docs.rs/discrete_range_map/latest/discrete_range_map/range_bounds_set/struct.DiscreteRangeSet.html
 //! [`copse`]: https://github.com/eggyal/copse
@@ -275,18 +287,31 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 
 extern crate alloc;
 
+pub(crate) mod augmented_tree;
 pub mod test_ranges;
 pub(crate) mod utils;
 
 pub mod discrete_finite;
+pub mod discrete_interval_multi_map;
 pub mod interval;
 
+pub mod discrete_range_aggregate_map;
+pub mod discrete_range_counter;
 pub mod discrete_range_map;
 pub mod discrete_range_set;
 
-pub use crate::discrete_finite::DiscreteFinite;
+#[cfg(feature = "net")]
+pub mod net;
+
+pub use crate::discrete_finite::{DiscreteFinite, DiscreteMeasure};
+pub use crate::discrete_interval_multi_map::DiscreteIntervalMultiMap;
+pub use crate::discrete_range_aggregate_map::{
+	Aggregate, DiscreteRangeAggregateMap, Monoid,
+};
+pub use crate::discrete_range_counter::DiscreteRangeCounter;
 pub use crate::discrete_range_map::{
-	DiscreteRangeMap, InclusiveRange, OverlapError, PointType, RangeType,
+	DiscreteRangeMap, InclusiveRange, InsertError, InvalidRangeError,
+	OverlapError, PointType, RangeType, ShiftOverflowError,
 };
 pub use crate::discrete_range_set::DiscreteRangeSet;
 pub use crate::interval::InclusiveInterval;
@@ -0,0 +1,316 @@
+//! [`DiscreteFinite`] and [`DiscreteMeasure`] implementations for
+//! [`Ipv4Addr`] and [`Ipv6Addr`] along with CIDR helpers for
+//! [`DiscreteRangeSet`]s of them.
+//!
+//! This module is only available with the `net` feature enabled.
+
+use core::error::Error;
+use core::fmt;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::discrete_range_map::RangeType;
+use crate::{
+	DiscreteFinite, DiscreteMeasure, DiscreteRangeSet, InclusiveInterval,
+};
+
+/// An error type to represent a string that is not a valid CIDR block
+/// such as `"10.0.0.0/8"`.
+///
+/// The address must be valid, the prefix length must not be larger
+/// than the address's bit length and the address must not have any
+/// bits set after the prefix.
+#[derive(PartialEq, Debug)]
+pub struct ParseCidrError;
+
+impl DiscreteFinite for Ipv4Addr {
+	const MIN: Self = Ipv4Addr::new(0, 0, 0, 0);
+	const MAX: Self = Ipv4Addr::new(255, 255, 255, 255);
+
+	fn up(self) -> Option<Self> {
+		u32::from(self).up().map(Ipv4Addr::from)
+	}
+	fn down(self) -> Option<Self> {
+		u32::from(self).down().map(Ipv4Addr::from)
+	}
+	fn up_by(self, n: u128) -> Option<Self> {
+		u32::from(self).up_by(n).map(Ipv4Addr::from)
+	}
+	fn down_by(self, n: u128) -> Option<Self> {
+		u32::from(self).down_by(n).map(Ipv4Addr::from)
+	}
+}
+
+impl DiscreteFinite for Ipv6Addr {
+	const MIN: Self = Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0);
+	const MAX: Self = Ipv6Addr::new(
+		0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+	);
+
+	fn up(self) -> Option<Self> {
+		u128::from(self).up().map(Ipv6Addr::from)
+	}
+	fn down(self) -> Option<Self> {
+		u128::from(self).down().map(Ipv6Addr::from)
+	}
+	fn up_by(self, n: u128) -> Option<Self> {
+		u128::from(self).up_by(n).map(Ipv6Addr::from)
+	}
+	fn down_by(self, n: u128) -> Option<Self> {
+		u128::from(self).down_by(n).map(Ipv6Addr::from)
+	}
+}
+
+impl DiscreteMeasure for Ipv4Addr {
+	fn distance(from: Self, to: Self) -> u128 {
+		u32::distance(u32::from(from), u32::from(to))
+	}
+}
+
+impl DiscreteMeasure for Ipv6Addr {
+	fn distance(from: Self, to: Self) -> u128 {
+		u128::distance(u128::from(from), u128::from(to))
+	}
+}
+
+impl<K> DiscreteRangeSet<Ipv4Addr, K>
+where
+	K: RangeType<Ipv4Addr>,
+{
+	/// Adds the range of addresses in the given CIDR block to the set,
+	/// merging it with any ranges it touches or overlaps, and returns
+	/// the merged range.
+	///
+	/// See [`DiscreteRangeSet::insert_merge_touching_or_overlapping()`]
+	/// for more details.
+	///
+	/// If the given string is not a valid CIDR block then a
+	/// [`ParseCidrError`] is returned and the set is not updated.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::Ipv4Addr;
+	///
+	/// use discrete_range_map::net::ParseCidrError;
+	/// use discrete_range_map::{DiscreteRangeSet, InclusiveInterval};
+	///
+	/// let mut set: DiscreteRangeSet<
+	/// 	Ipv4Addr,
+	/// 	InclusiveInterval<Ipv4Addr>,
+	/// > = DiscreteRangeSet::new();
+	///
+	/// assert_eq!(
+	/// 	set.insert_cidr("10.0.0.0/8"),
+	/// 	Ok(InclusiveInterval {
+	/// 		start: Ipv4Addr::new(10, 0, 0, 0),
+	/// 		end: Ipv4Addr::new(10, 255, 255, 255),
+	/// 	})
+	/// );
+	/// assert_eq!(set.insert_cidr("10.0.0.1/8"), Err(ParseCidrError));
+	/// assert_eq!(set.insert_cidr("10.0.0.0/33"), Err(ParseCidrError));
+	/// assert_eq!(set.insert_cidr("10.0.0.0/08"), Err(ParseCidrError));
+	/// assert_eq!(set.insert_cidr("10.0.0.0/+8"), Err(ParseCidrError));
+	/// ```
+	pub fn insert_cidr(&mut self, cidr: &str) -> Result<K, ParseCidrError> {
+		let (start, prefix_length) = parse_cidr::<Ipv4Addr>(cidr, 32)?;
+		let (start, end) =
+			cidr_bounds(u32::from(start).into(), prefix_length, 32)?;
+
+		Ok(self.insert_merge_touching_or_overlapping(K::from(
+			InclusiveInterval {
+				start: Ipv4Addr::from(start as u32),
+				end: Ipv4Addr::from(end as u32),
+			},
+		)))
+	}
+
+	/// Returns an iterator over the minimal list of CIDR blocks
+	/// covering each range in the set, as pairs of the block's first
+	/// address and its prefix length, in ascending order.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::Ipv4Addr;
+	///
+	/// use discrete_range_map::{DiscreteRangeSet, InclusiveInterval};
+	///
+	/// let set =
+	/// 	DiscreteRangeSet::from_slice_strict([InclusiveInterval {
+	/// 		start: Ipv4Addr::new(10, 0, 0, 1),
+	/// 		end: Ipv4Addr::new(10, 0, 0, 6),
+	/// 	}])
+	/// 	.unwrap();
+	///
+	/// assert_eq!(
+	/// 	set.to_cidrs().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(Ipv4Addr::new(10, 0, 0, 1), 32),
+	/// 		(Ipv4Addr::new(10, 0, 0, 2), 31),
+	/// 		(Ipv4Addr::new(10, 0, 0, 4), 31),
+	/// 		(Ipv4Addr::new(10, 0, 0, 6), 32),
+	/// 	]
+	/// );
+	/// ```
+	pub fn to_cidrs(&self) -> impl Iterator<Item = (Ipv4Addr, u8)> + '_ {
+		self.iter().flat_map(|range| {
+			cidr_blocks(
+				u32::from(range.start()).into(),
+				u32::from(range.end()).into(),
+				32,
+			)
+			.map(|(start, prefix_length)| {
+				(Ipv4Addr::from(start as u32), prefix_length)
+			})
+		})
+	}
+}
+
+impl<K> DiscreteRangeSet<Ipv6Addr, K>
+where
+	K: RangeType<Ipv6Addr>,
+{
+	/// Adds the range of addresses in the given CIDR block to the set,
+	/// merging it with any ranges it touches or overlaps, and returns
+	/// the merged range.
+	///
+	/// See the [`Ipv4Addr`] version of
+	/// [`insert_cidr()`](DiscreteRangeSet#method.insert_cidr) for more
+	/// details.
+	///
+	/// # Examples
+	/// ```
+	/// use std::net::Ipv6Addr;
+	///
+	/// use discrete_range_map::{DiscreteRangeSet, InclusiveInterval};
+	///
+	/// let mut set: DiscreteRangeSet<
+	/// 	Ipv6Addr,
+	/// 	InclusiveInterval<Ipv6Addr>,
+	/// > = DiscreteRangeSet::new();
+	///
+	/// assert_eq!(
+	/// 	set.insert_cidr("2001:db8::/32"),
+	/// 	Ok(InclusiveInterval {
+	/// 		start: "2001:db8::".parse::<Ipv6Addr>().unwrap(),
+	/// 		end: "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"
+	/// 			.parse()
+	/// 			.unwrap(),
+	/// 	})
+	/// );
+	/// ```
+	pub fn insert_cidr(&mut self, cidr: &str) -> Result<K, ParseCidrError> {
+		let (start, prefix_length) = parse_cidr::<Ipv6Addr>(cidr, 128)?;
+		let (start, end) = cidr_bounds(u128::from(start), prefix_length, 128)?;
+
+		Ok(self.insert_merge_touching_or_overlapping(K::from(
+			InclusiveInterval {
+				start: Ipv6Addr::from(start),
+				end: Ipv6Addr::from(end),
+			},
+		)))
+	}
+
+	/// Returns an iterator over the minimal list of CIDR blocks
+	/// covering each range in the set, as pairs of the block's first
+	/// address and its prefix length, in ascending order.
+	///
+	/// See the [`Ipv4Addr`] version of
+	/// [`to_cidrs()`](DiscreteRangeSet#method.to_cidrs) for more
+	/// details.
+	pub fn to_cidrs(&self) -> impl Iterator<Item = (Ipv6Addr, u8)> + '_ {
+		self.iter().flat_map(|range| {
+			cidr_blocks(u128::from(range.start()), u128::from(range.end()), 128)
+				.map(|(start, prefix_length)| {
+					(Ipv6Addr::from(start), prefix_length)
+				})
+		})
+	}
+}
+
+// Helper Functions ==========================
+
+fn parse_cidr<A>(cidr: &str, bits: u8) -> Result<(A, u8), ParseCidrError>
+where
+	A: core::str::FromStr,
+{
+	let (address, prefix_length) =
+		cidr.split_once('/').ok_or(ParseCidrError)?;
+
+	let address = address.parse().map_err(|_| ParseCidrError)?;
+	// `u8::from_str()` also accepts a leading `+` or leading zeros
+	if !prefix_length.bytes().all(|byte| byte.is_ascii_digit())
+		|| (prefix_length.starts_with('0') && prefix_length != "0")
+	{
+		return Err(ParseCidrError);
+	}
+	let prefix_length =
+		prefix_length.parse::<u8>().map_err(|_| ParseCidrError)?;
+
+	if prefix_length > bits {
+		return Err(ParseCidrError);
+	}
+
+	return Ok((address, prefix_length));
+}
+
+/// Returns the first and last addresses of the CIDR block starting at
+/// `start`, or an error if `start` has any bits set after the prefix.
+fn cidr_bounds(
+	start: u128,
+	prefix_length: u8,
+	bits: u8,
+) -> Result<(u128, u128), ParseCidrError> {
+	let host_mask = low_bits(bits - prefix_length);
+
+	if start & host_mask != 0 {
+		return Err(ParseCidrError);
+	}
+
+	return Ok((start, start | host_mask));
+}
+
+/// Decomposes the inclusive range `start..=end` of `bits`-bit addresses
+/// into the minimal list of CIDR blocks covering it.
+fn cidr_blocks(
+	start: u128,
+	end: u128,
+	bits: u8,
+) -> impl Iterator<Item = (u128, u8)> {
+	let mut next = Some(start);
+
+	core::iter::from_fn(move || {
+		let start = next?;
+
+		// the largest block must be both aligned to `start` and fit
+		// within the remaining range
+		let aligned = start.trailing_zeros().min(u32::from(bits));
+		let fits = match (end - start).checked_add(1) {
+			Some(len) => len.ilog2(),
+			None => 128,
+		};
+		let size = aligned.min(fits) as u8;
+
+		let block_end = start + low_bits(size);
+		next = if block_end == end {
+			None
+		} else {
+			Some(block_end + 1)
+		};
+
+		Some((start, bits - size))
+	})
+}
+
+/// Returns a mask with the lowest `n` bits set.
+fn low_bits(n: u8) -> u128 {
+	u128::MAX.checked_shr(128 - u32::from(n)).unwrap_or(0)
+}
+
+// Trait Impls ==========================
+
+impl fmt::Display for ParseCidrError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "invalid CIDR block")
+	}
+}
+
+impl Error for ParseCidrError {}
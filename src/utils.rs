@@ -18,6 +18,7 @@ along with discrete_range_map. If not, see <https://www.gnu.org/licenses/>.
 */
 
 use core::cmp::Ordering;
+use core::iter::Peekable;
 
 use crate::discrete_range_map::{PointType, RangeType};
 use crate::interval::InclusiveInterval;
@@ -213,3 +214,122 @@ where
 {
 	!matches!(sorted_config(a, b), SortedConfig::NonOverlapping(_, _))
 }
+
+/// Joins two ascending ranges into one if they touch, for use with
+/// [`Itertools::coalesce()`](itertools::Itertools::coalesce).
+pub(crate) fn join_touching<I>(
+	first: InclusiveInterval<I>,
+	second: InclusiveInterval<I>,
+) -> Result<InclusiveInterval<I>, (InclusiveInterval<I>, InclusiveInterval<I>)>
+where
+	I: PointType,
+{
+	if first.end.up() == Some(second.start) {
+		Ok(InclusiveInterval {
+			start: first.start,
+			end: second.end,
+		})
+	} else {
+		Err((first, second))
+	}
+}
+
+/// Walks two ascending iterators of non-overlapping ranges in lock-step,
+/// yielding every maximal segment over which the coverage of both
+/// iterators doesn't change along with the values covering it.
+///
+/// Segments that neither iterator covers are skipped.
+pub(crate) struct Sweep<I, A, B>
+where
+	A: Iterator,
+	B: Iterator,
+{
+	a: Peekable<A>,
+	b: Peekable<B>,
+	position: Option<I>,
+}
+pub(crate) fn sweep<I, K1, K2, X, Y, A, B>(a: A, b: B) -> Sweep<I, A, B>
+where
+	I: PointType,
+	K1: RangeType<I>,
+	K2: RangeType<I>,
+	A: Iterator<Item = (K1, X)>,
+	B: Iterator<Item = (K2, Y)>,
+{
+	Sweep {
+		a: a.peekable(),
+		b: b.peekable(),
+		position: Some(I::MIN),
+	}
+}
+impl<I, K1, K2, X, Y, A, B> Iterator for Sweep<I, A, B>
+where
+	I: PointType,
+	K1: RangeType<I>,
+	K2: RangeType<I>,
+	X: Clone,
+	Y: Clone,
+	A: Iterator<Item = (K1, X)>,
+	B: Iterator<Item = (K2, Y)>,
+{
+	type Item = (InclusiveInterval<I>, Option<X>, Option<Y>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut position = self.position?;
+
+		//throw away the ranges we have already walked past
+		while self
+			.a
+			.next_if(|(range, _)| range.end() < position)
+			.is_some()
+		{}
+		while self
+			.b
+			.next_if(|(range, _)| range.end() < position)
+			.is_some()
+		{}
+
+		let a_start = self.a.peek().map(|(range, _)| range.start());
+		let b_start = self.b.peek().map(|(range, _)| range.start());
+
+		//skip over any gap that neither side covers
+		position = match (a_start, b_start) {
+			(None, None) => return None,
+			(Some(a_start), None) => position.max(a_start),
+			(None, Some(b_start)) => position.max(b_start),
+			(Some(a_start), Some(b_start)) => {
+				position.max(a_start.min(b_start))
+			}
+		};
+
+		let (a_value, a_boundary) = side_at(self.a.peek(), position);
+		let (b_value, b_boundary) = side_at(self.b.peek(), position);
+
+		let end = a_boundary.min(b_boundary);
+		self.position = end.up();
+
+		Some((
+			InclusiveInterval {
+				start: position,
+				end,
+			},
+			a_value,
+			b_value,
+		))
+	}
+}
+fn side_at<I, K, X>(head: Option<&(K, X)>, position: I) -> (Option<X>, I)
+where
+	I: PointType,
+	K: RangeType<I>,
+	X: Clone,
+{
+	match head {
+		Some((range, value)) if range.start() <= position => {
+			(Some(value.clone()), range.end())
+		}
+		//the head range must start after position so down() can't fail
+		Some((range, _)) => (None, range.start().down().unwrap()),
+		None => (None, I::MAX),
+	}
+}